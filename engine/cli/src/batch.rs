@@ -0,0 +1,99 @@
+use std::path::{Path, PathBuf};
+
+use pna_core::api::{BatchError, BatchItem, BatchReport, GradebookInfo, GradingPolicy};
+use pna_core::batch::{item, report, PassRate};
+use rayon::prelude::*;
+
+use crate::{input, print_json, render, Format};
+
+/// Carga los libros en paralelo; los que fallan quedan en `errors` sin detener el resto.
+pub fn run(files: &[PathBuf], policy: Option<&GradingPolicy>) -> BatchReport {
+    let results: Vec<Result<BatchItem, BatchError>> = files.par_iter().map(|path| load(path, policy)).collect();
+
+    let (mut items, mut errors) = (Vec::new(), Vec::new());
+    for result in results {
+        match result {
+            Ok(item) => items.push(item),
+            Err(error) => errors.push(error),
+        }
+    }
+    report(items, errors)
+}
+
+fn load(path: &Path, policy: Option<&GradingPolicy>) -> Result<BatchItem, BatchError> {
+    let file = path.display().to_string();
+    let state = input::load(path, policy).map_err(|message| BatchError { file: file.clone(), message })?;
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let info = GradebookInfo::from_headers(&state.description_headers, &stem);
+    Ok(item(&file, info, &state.table, &state.policy, state.attendance.as_ref()))
+}
+
+pub fn print(report: &BatchReport, format: Format) -> Result<(), String> {
+    if let Format::Json = format {
+        return print_json(report);
+    }
+
+    let sections = [
+        ("Gradebook", &report.gradebooks),
+        ("Course", &report.by_course),
+        ("Section", &report.by_section),
+        ("Teacher", &report.by_teacher),
+    ];
+    for (title, rates) in sections {
+        if rates.is_empty() {
+            continue;
+        }
+        println!("{}\n", pass_rates(title, rates));
+    }
+
+    if !report.unusual_failure_rates.is_empty() {
+        println!("Unusual failure rates");
+        for unusual in &report.unusual_failure_rates {
+            println!(
+                "  {} ({}): {} failed, others {} (z = {:.1})",
+                unusual.info.course,
+                unusual.file,
+                render::percent(Some(unusual.failure_rate * 100.0)),
+                render::percent(Some(unusual.others_mean * 100.0)),
+                unusual.z_score
+            );
+        }
+        println!();
+    }
+
+    if !report.at_risk_students.is_empty() {
+        let rows: Vec<Vec<String>> = report.at_risk_students.iter().map(|student| {
+            let courses = student.courses.iter()
+                .map(|c| format!("{} ({:?})", c.course, c.status))
+                .collect::<Vec<_>>()
+                .join(", ");
+            vec![
+                student.carnet.clone(),
+                student.name.clone(),
+                student.failed_count.to_string(),
+                student.at_risk_count.to_string(),
+                courses,
+            ]
+        }).collect();
+        println!("{}\n", render::table(&["Carnet", "Name", "Failed", "At risk", "Courses"], &rows, &[2, 3]));
+    }
+
+    for error in &report.errors {
+        eprintln!("{}: {}", error.file, error.message);
+    }
+    Ok(())
+}
+
+fn pass_rates(title: &str, rates: &[PassRate]) -> String {
+    let rows: Vec<Vec<String>> = rates.iter().map(|rate| vec![
+        rate.key.clone(),
+        rate.gradebook_count.to_string(),
+        rate.student_count.to_string(),
+        rate.approved_count.to_string(),
+        rate.at_risk_count.to_string(),
+        rate.failed_count.to_string(),
+        render::percent(rate.pass_rate.map(|r| r * 100.0)),
+        render::percent(rate.failure_rate.map(|r| r * 100.0)),
+    ]).collect();
+    render::table(&[title, "Files", "Students", "Approved", "At risk", "Failed", "Pass", "Fail"], &rows, &[1, 2, 3, 4, 5, 6, 7])
+}
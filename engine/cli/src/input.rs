@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use pna_core::api::{EngineState, GradingPolicy};
+
+/// Extensiones que se abren como libro de notas; `.pna` es una sesión guardada.
+const GRADEBOOK_EXTENSIONS: &[&str] = &["xlsx", "xlsm", "xls", "ods", "pna"];
+
+/// Lee un libro (o una sesión `.pna`). `policy` reemplaza el reglamento guardado en la sesión.
+pub fn load(path: &Path, policy: Option<&GradingPolicy>) -> Result<EngineState, String> {
+    let data = fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut state = if has_extension(path, "pna") {
+        EngineState::from_bytes(&data).map_err(|e| e.to_string())?
+    } else {
+        EngineState::from_workbook(&data).map_err(|e| e.to_string())?
+    };
+    if let Some(policy) = policy {
+        state.policy = policy.clone();
+    }
+    Ok(state)
+}
+
+pub fn load_policy(path: &Path) -> Result<GradingPolicy, String> {
+    let json = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    serde_json::from_str(&json).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Expande las carpetas a los libros que contienen (sin entrar en subcarpetas), en orden.
+pub fn gradebook_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    for path in paths {
+        if !path.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+        let mut found: Vec<PathBuf> = fs::read_dir(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_file() && is_gradebook(p))
+            .collect();
+        found.sort();
+        files.extend(found);
+    }
+    Ok(files)
+}
+
+fn is_gradebook(path: &Path) -> bool {
+    // Archivos de bloqueo de Excel y LibreOffice
+    let is_lock_file = path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("~$") || name.starts_with(".~lock"));
+    !is_lock_file && GRADEBOOK_EXTENSIONS.iter().any(|extension| has_extension(path, extension))
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case(extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradebook_files() {
+        assert!(is_gradebook(Path::new("notas/Cálculo I.XLSX")));
+        assert!(is_gradebook(Path::new("sesion.pna")));
+        assert!(!is_gradebook(Path::new("notas/~$Cálculo I.xlsx")));
+        assert!(!is_gradebook(Path::new("notas/lista.csv")));
+
+        let files = gradebook_files(&[PathBuf::from("missing.xlsx")]).unwrap();
+        assert_eq!(files, vec![PathBuf::from("missing.xlsx")]);
+    }
+}
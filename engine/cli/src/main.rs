@@ -0,0 +1,375 @@
+//! `pna`: el motor de notas desde la terminal, para procesar libros en lote o en scripts.
+
+mod batch;
+mod input;
+mod render;
+mod xlsx;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use pna_core::api::{
+    detect_anomalies, diff_with_stats, AcademicStatus, Anomaly, AnomalyKind, CarnetFormat, EngineState, GradeStats,
+    GradebookDiff, GradingPolicy, Severity,
+};
+use pna_core::export::{to_csv, to_html};
+use pna_core::models::domain::StudentSummary;
+use serde::Serialize;
+
+#[derive(Parser)]
+#[command(name = "pna", version, about = "Grade analysis for PNA gradebooks")]
+struct Cli {
+    /// Grading policy as JSON; replaces the one saved in `.pna` sessions.
+    #[arg(long, global = true)]
+    policy: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Class and evaluation summary.
+    Summary {
+        file: PathBuf,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Students with their accumulated score and status.
+    Students {
+        file: PathBuf,
+        /// Only these statuses (comma separated).
+        #[arg(long, value_enum, value_delimiter = ',')]
+        status: Vec<Status>,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Checks gradebooks (files or folders) for impossible or suspicious values.
+    Validate {
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// Accepted carnet patterns (see `CarnetFormat`); without it carnets are not checked.
+        #[arg(long = "carnet-pattern")]
+        carnet_patterns: Vec<String>,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Writes the table with the computed columns.
+    Export {
+        file: PathBuf,
+        #[arg(long, value_enum)]
+        format: ExportFormat,
+        /// Output file; csv and html go to stdout without it.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Aggregate report over every gradebook in the given files or folders.
+    Batch {
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// What changed between two versions of a gradebook.
+    Diff {
+        old: PathBuf,
+        new: PathBuf,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Table,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Csv,
+    Xlsx,
+    Html,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Status {
+    Approved,
+    OnTrack,
+    Warning,
+    Critical,
+    Failed,
+}
+
+impl From<Status> for AcademicStatus {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Approved => AcademicStatus::Approved,
+            Status::OnTrack => AcademicStatus::OnTrack,
+            Status::Warning => AcademicStatus::Warning,
+            Status::Critical => AcademicStatus::Critical,
+            Status::Failed => AcademicStatus::Failed,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<ExitCode, String> {
+    let policy = cli.policy.as_deref().map(input::load_policy).transpose()?;
+    let policy = policy.as_ref();
+
+    match cli.command {
+        Command::Summary { file, format } => summary(&input::load(&file, policy)?, format)?,
+        Command::Students { file, status, format } => {
+            let statuses: Vec<AcademicStatus> = status.into_iter().map(AcademicStatus::from).collect();
+            students(&input::load(&file, policy)?, &statuses, format)?
+        }
+        Command::Validate { paths, carnet_patterns, format } => {
+            let carnets = (!carnet_patterns.is_empty())
+                .then(|| CarnetFormat { patterns: carnet_patterns, ..CarnetFormat::default() });
+            return validate(&input::gradebook_files(&paths)?, policy, carnets.as_ref(), format);
+        }
+        Command::Export { file, format, output } => export(&file, &input::load(&file, policy)?, format, output.as_deref())?,
+        Command::Batch { paths, format } => {
+            let files = input::gradebook_files(&paths)?;
+            let report = batch::run(&files, policy);
+            batch::print(&report, format)?;
+            // El reporte sale igual; el código avisa a los scripts que faltó algún archivo
+            if !report.errors.is_empty() {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Diff { old, new, format } => {
+            let old = input::load(&old, policy)?;
+            let new = input::load(&new, policy)?;
+            // El reglamento del libro nuevo decide los estados de ambos
+            let old_stats = GradeStats::with_attendance(&old.table, &new.policy, old.attendance.as_ref())
+                .with_makeup(&old.makeup_grades);
+            let result = diff_with_stats(&old_stats, &new.stats());
+            print_diff(&result, &new.table.evaluations, format)?;
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), String> {
+    println!("{}", serde_json::to_string_pretty(value).map_err(|e| e.to_string())?);
+    Ok(())
+}
+
+fn summary(state: &EngineState, format: Format) -> Result<(), String> {
+    let summary = state.stats().summary();
+    if let Format::Json = format {
+        return print_json(&summary);
+    }
+
+    for line in &state.description_headers {
+        println!("{}", line);
+    }
+    let class = &summary.class;
+    println!("Students: {}    Average: {}    Std dev: {}", class.student_count, render::number(class.overall_average), render::number(class.overall_std_dev));
+    println!(
+        "Approved: {}    On track: {}    Warning: {}    Critical: {}    Failed: {}",
+        class.approved_count, class.on_track_count, class.warning_count, class.critical_count, class.failed_count
+    );
+    println!();
+
+    let rows: Vec<Vec<String>> = summary.evaluations.iter().map(|e| vec![
+        e.name.clone(),
+        render::number(e.max_possible_score),
+        render::number(e.average),
+        render::number(e.std_dev),
+        render::number(e.lowest_score),
+        render::number(e.highest_score),
+        e.evaluated_count.to_string(),
+        e.missing_count.to_string(),
+    ]).collect();
+    println!("{}", render::table(&["Evaluation", "Points", "Average", "Std dev", "Lowest", "Highest", "Evaluated", "Missing"], &rows, &[1, 2, 3, 4, 5, 6, 7]));
+    Ok(())
+}
+
+fn students(state: &EngineState, statuses: &[AcademicStatus], format: Format) -> Result<(), String> {
+    let summaries = state.stats().student_summaries();
+    // `id` es el carnet
+    let selected: Vec<&StudentSummary> = summaries.iter()
+        .filter(|s| statuses.is_empty() || statuses.contains(&s.status))
+        .collect();
+
+    if let Format::Json = format {
+        return print_json(&selected);
+    }
+    let rows: Vec<Vec<String>> = selected.iter().map(|s| vec![
+        s.id.clone(),
+        s.name.clone(),
+        render::number(s.accumulated_score),
+        format!("{:?}", s.status),
+        render::percent(s.attendance_percent),
+        s.converted_grade.as_ref().map(|g| g.label.clone()).unwrap_or_default(),
+    ]).collect();
+    println!("{}", render::table(&["Carnet", "Name", "Score", "Status", "Attendance", "Grade"], &rows, &[2, 4]));
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct Issue {
+    severity: Severity,
+    carnet: Option<String>,
+    evaluation: Option<String>,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct FileReport {
+    path: PathBuf,
+    /// The file could not be read at all.
+    error: Option<String>,
+    issues: Vec<Issue>,
+}
+
+/// Falla (código 1) si algún archivo no se pudo leer o tiene errores.
+fn validate(files: &[PathBuf], policy: Option<&GradingPolicy>, carnets: Option<&CarnetFormat>, format: Format) -> Result<ExitCode, String> {
+    let reports: Vec<FileReport> = files.iter().map(|path| match input::load(path, policy) {
+        Ok(state) => FileReport { path: path.clone(), error: None, issues: issues(&state, carnets) },
+        Err(error) => FileReport { path: path.clone(), error: Some(error), issues: Vec::new() },
+    }).collect();
+
+    if let Format::Json = format {
+        print_json(&reports)?;
+    } else {
+        for report in &reports {
+            match &report.error {
+                Some(error) => println!("{}: unreadable: {}", report.path.display(), error),
+                None if report.issues.is_empty() => println!("{}: ok", report.path.display()),
+                None => {
+                    println!("{}: {} issue(s)", report.path.display(), report.issues.len());
+                    for issue in &report.issues {
+                        let location = [issue.carnet.as_deref(), issue.evaluation.as_deref()]
+                            .into_iter().flatten().collect::<Vec<_>>().join(" / ");
+                        match location.as_str() {
+                            "" => println!("  {:?}: {}", issue.severity, issue.message),
+                            location => println!("  {:?} {}: {}", issue.severity, location, issue.message),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let failed = reports.iter()
+        .any(|r| r.error.is_some() || r.issues.iter().any(|i| i.severity == Severity::Error));
+    Ok(if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
+
+fn issues(state: &EngineState, carnets: Option<&CarnetFormat>) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = detect_anomalies(&state.table).into_iter().map(anomaly_issue).collect();
+
+    let records = &state.table.records;
+    for (row, record) in records.iter().enumerate() {
+        let carnet = record.carnet.trim();
+        if carnet.is_empty() {
+            issues.push(Issue { severity: Severity::Error, carnet: None, evaluation: None, message: format!("row {} has no carnet", row + 1) });
+            continue;
+        }
+        if records[..row].iter().any(|r| r.carnet.trim() == carnet) {
+            issues.push(Issue { severity: Severity::Error, carnet: Some(carnet.to_string()), evaluation: None, message: "duplicate carnet".to_string() });
+        }
+        if let Some(format) = carnets.filter(|f| f.parse(carnet).is_err()) {
+            let message = format!("carnet does not match {}", format.patterns.join(", "));
+            issues.push(Issue { severity: Severity::Warning, carnet: Some(carnet.to_string()), evaluation: None, message });
+        }
+    }
+    issues
+}
+
+fn anomaly_issue(anomaly: Anomaly) -> Issue {
+    let message = match anomaly.anomaly {
+        AnomalyKind::ObtainedExceedsTotal { obtained, total } => format!("{} out of {} is above the total", obtained, total),
+        AnomalyKind::NegativeScore { value } => format!("negative score {}", value),
+        AnomalyKind::AboveEvaluationMax { value, max } => format!("{} is above the evaluation maximum {}", value, max),
+        AnomalyKind::ColumnOutlier { value, median, .. } => format!("{} is far from the median {}", value, median),
+        AnomalyKind::IdenticalRow { value, count } => format!("same score {} in {} evaluations", value, count),
+    };
+    Issue { severity: anomaly.severity, carnet: Some(anomaly.carnet), evaluation: anomaly.evaluation, message }
+}
+
+fn export(file: &Path, state: &EngineState, format: ExportFormat, output: Option<&Path>) -> Result<(), String> {
+    let text = match format {
+        ExportFormat::Csv => to_csv(state),
+        ExportFormat::Html => {
+            let title = state.description_headers.first().cloned()
+                .unwrap_or_else(|| file.file_stem().unwrap_or_default().to_string_lossy().into_owned());
+            to_html(&title, state)
+        }
+        ExportFormat::Xlsx => {
+            let output = output.ok_or("xlsx export needs --output")?;
+            return xlsx::write(output, state);
+        }
+    };
+    match output {
+        Some(path) => fs::write(path, text).map_err(|e| format!("{}: {}", path.display(), e)),
+        None => {
+            print!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+/// `evaluations` son las del libro nuevo, al que se refieren las celdas cambiadas.
+fn print_diff(diff: &GradebookDiff, evaluations: &[String], format: Format) -> Result<(), String> {
+    if let Format::Json = format {
+        return print_json(diff);
+    }
+    if diff.is_empty() {
+        println!("No changes");
+        return Ok(());
+    }
+
+    for student in &diff.added_students {
+        println!("+ student {} {}", student.carnet, student.name);
+    }
+    for student in &diff.removed_students {
+        println!("- student {} {}", student.carnet, student.name);
+    }
+    for evaluation in &diff.added_evaluations {
+        println!("+ evaluation {}", evaluation);
+    }
+    for evaluation in &diff.removed_evaluations {
+        println!("- evaluation {}", evaluation);
+    }
+    for rename in &diff.renamed_evaluations {
+        println!("~ evaluation {} -> {}", rename.old_name, rename.new_name);
+    }
+
+    if !diff.changed_cells.is_empty() {
+        println!();
+        let rows: Vec<Vec<String>> = diff.changed_cells.iter()
+            .map(|c| vec![
+                c.carnet.clone(),
+                evaluations.get(c.column).cloned().unwrap_or_default(),
+                c.old_value.to_string(),
+                c.new_value.to_string(),
+            ])
+            .collect();
+        println!("{}", render::table(&["Carnet", "Evaluation", "Before", "After"], &rows, &[2, 3]));
+    }
+    if !diff.status_changes.is_empty() {
+        println!();
+        let rows: Vec<Vec<String>> = diff.status_changes.iter()
+            .map(|c| vec![c.carnet.clone(), format!("{:?}", c.old_status), format!("{:?}", c.new_status)])
+            .collect();
+        println!("{}", render::table(&["Carnet", "Before", "After"], &rows, &[]));
+    }
+    Ok(())
+}
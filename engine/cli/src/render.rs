@@ -0,0 +1,56 @@
+//! Salida de texto para la terminal.
+
+/// Tabla con columnas alineadas; las columnas en `right` (índices) se alinean a la derecha.
+pub fn table(header: &[&str], rows: &[Vec<String>], right: &[usize]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: &mut dyn Iterator<Item = &str>| {
+        let padded: Vec<String> = cells.zip(&widths).enumerate().map(|(i, (cell, width))| {
+            if right.contains(&i) {
+                format!("{:>width$}", cell, width = width)
+            } else {
+                format!("{:<width$}", cell, width = width)
+            }
+        }).collect();
+        padded.join("  ").trim_end().to_string()
+    };
+
+    let mut lines = vec![line(&mut header.iter().copied())];
+    lines.push(widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>().join("  "));
+    for row in rows {
+        lines.push(line(&mut row.iter().map(String::as_str)));
+    }
+    lines.join("\n")
+}
+
+pub fn number(value: Option<f32>) -> String {
+    value.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string())
+}
+
+pub fn percent(value: Option<f32>) -> String {
+    value.map(|v| format!("{:.0}%", v)).unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aligned_table() {
+        let rows = vec![
+            vec!["A1".to_string(), "Ana".to_string(), "9.50".to_string()],
+            vec!["A10".to_string(), "Luis Pérez".to_string(), "10.00".to_string()],
+        ];
+        let text = table(&["Carnet", "Nombre", "Nota"], &rows, &[2]);
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "Carnet  Nombre       Nota");
+        assert_eq!(lines[2], "A1      Ana          9.50");
+        assert_eq!(lines[3], "A10     Luis Pérez  10.00");
+    }
+}
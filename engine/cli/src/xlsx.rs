@@ -0,0 +1,54 @@
+use std::ops::Range;
+use std::path::Path;
+
+use pna_core::api::EngineState;
+use pna_core::export::rows;
+use rust_xlsxwriter::{Format, Workbook};
+
+/// Misma tabla que `export::to_csv`, con las notas y el acumulado como números.
+pub fn write(path: &Path, state: &EngineState) -> Result<(), String> {
+    let (header, rows) = rows(state);
+    let numeric = numeric_columns(&header, state.table.evaluations.len());
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Notas").map_err(|e| e.to_string())?;
+
+    let bold = Format::new().set_bold();
+    for (col, cell) in header.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, cell, &bold).map_err(|e| e.to_string())?;
+    }
+    for (row, cells) in rows.iter().enumerate() {
+        let row = row as u32 + 1;
+        for (col, cell) in cells.iter().enumerate() {
+            let col = col as u16;
+            // Carnets o grupos con sólo dígitos siguen siendo texto
+            match cell.parse::<f64>() {
+                Ok(number) if numeric.contains(&(col as usize)) => sheet.write_number(row, col, number),
+                _ => sheet.write_string(row, col, cell),
+            }.map_err(|e| e.to_string())?;
+        }
+    }
+    sheet.set_freeze_panes(1, 0).map_err(|e| e.to_string())?;
+
+    workbook.save(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Columnas de las notas y el "Acumulado", que terminan justo antes de "Estado".
+fn numeric_columns(header: &[String], evaluations: usize) -> Range<usize> {
+    let accumulated = header.iter().rposition(|h| h == "Acumulado").unwrap_or(header.len());
+    accumulated.saturating_sub(evaluations)..accumulated + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_columns() {
+        let header: Vec<String> = ["Carnet", "Nombre", "Correo", "Grupo", "Parcial", "Final", "Acumulado", "Estado", "Nota"]
+            .map(String::from).to_vec();
+        let numeric = numeric_columns(&header, 2);
+        assert_eq!(numeric, 4..7);
+        assert!(!numeric.contains(&0) && !numeric.contains(&8));
+    }
+}
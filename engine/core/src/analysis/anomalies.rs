@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::median;
+use crate::models::gradebook::{AcademicTable, GradeValue};
+use crate::models::stats::{compute_evaluation_max_scores, extract_raw_score};
+
+/// Umbral de Iglewicz y Hoaglin para el z robusto (0.6745 * |x - mediana| / MAD).
+const ROBUST_Z_THRESHOLD: f32 = 3.5;
+const MAD_SCALE: f32 = 0.6745;
+const MIN_OUTLIER_SAMPLE: usize = 5;
+const MIN_IDENTICAL_ROW: usize = 3;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub enum Severity {
+    Info,    // Vale la pena revisar
+    Warning, // Probablemente un error de digitación
+    Error,   // Imposible según las reglas de la hoja
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+#[serde(tag = "kind")]
+pub enum AnomalyKind {
+    ObtainedExceedsTotal { obtained: f32, total: f32 },
+    NegativeScore { value: f32 },
+    AboveEvaluationMax { value: f32, max: f32 },
+    ColumnOutlier { value: f32, median: f32, robust_z: f32 },
+    IdenticalRow { value: f32, count: usize },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct Anomaly {
+    pub severity: Severity,
+    pub anomaly: AnomalyKind,
+    /// Fila en `AcademicTable.records`.
+    pub row: usize,
+    /// Columna en `AcademicTable.evaluations`; None cuando afecta a toda la fila.
+    pub column: Option<usize>,
+    pub carnet: String,
+    pub evaluation: Option<String>,
+}
+
+/// Scans every cell and returns the findings, most severe first.
+pub fn detect_anomalies(table: &AcademicTable) -> Vec<Anomaly> {
+    let max_scores = compute_evaluation_max_scores(table);
+    let mut findings = Vec::new();
+
+    let finding = |severity, anomaly, row: usize, column: Option<usize>| Anomaly {
+        severity,
+        anomaly,
+        row,
+        column,
+        carnet: table.records[row].carnet.clone(),
+        evaluation: column.map(|c| table.evaluations[c].clone()),
+    };
+
+    // 1. Reglas por celda
+    for (row, record) in table.records.iter().enumerate() {
+        for (column, grade) in record.grades.iter().enumerate() {
+            match grade {
+                GradeValue::Fraction { obtained, total } if obtained > total => findings.push(finding(
+                    Severity::Error,
+                    AnomalyKind::ObtainedExceedsTotal { obtained: *obtained, total: *total },
+                    row,
+                    Some(column),
+                )),
+                GradeValue::Numeric(value) => {
+                    if let Some(max) = max_scores.get(column).copied().flatten().filter(|m| value > m) {
+                        findings.push(finding(
+                            Severity::Error,
+                            AnomalyKind::AboveEvaluationMax { value: *value, max },
+                            row,
+                            Some(column),
+                        ));
+                    }
+                }
+                _ => {}
+            }
+
+            if let Some(value) = extract_raw_score(grade).filter(|v| *v < 0.0) {
+                findings.push(finding(Severity::Error, AnomalyKind::NegativeScore { value }, row, Some(column)));
+            }
+        }
+    }
+
+    // 2. Valores atípicos dentro de cada columna
+    for column in 0..table.evaluations.len() {
+        let values: Vec<(usize, f32)> = table.records.iter().enumerate()
+            .filter_map(|(row, record)| record.grades.get(column).and_then(extract_raw_score).map(|v| (row, v)))
+            .collect();
+        if values.len() < MIN_OUTLIER_SAMPLE {
+            continue;
+        }
+
+        let raw: Vec<f32> = values.iter().map(|v| v.1).collect();
+        let Some(med) = median(&raw) else { continue };
+        let deviations: Vec<f32> = raw.iter().map(|v| (v - med).abs()).collect();
+        let Some(mad) = median(&deviations).filter(|m| *m > f32::EPSILON) else { continue };
+
+        for (row, value) in values {
+            let robust_z = MAD_SCALE * (value - med) / mad;
+            if robust_z.abs() > ROBUST_Z_THRESHOLD {
+                findings.push(finding(
+                    Severity::Warning,
+                    AnomalyKind::ColumnOutlier { value, median: med, robust_z },
+                    row,
+                    Some(column),
+                ));
+            }
+        }
+    }
+
+    // 3. Filas con exactamente la misma nota en todo (¿copiar y pegar?)
+    for (row, record) in table.records.iter().enumerate() {
+        let scores: Vec<f32> = record.grades.iter().filter_map(extract_raw_score).collect();
+        if scores.len() >= MIN_IDENTICAL_ROW && scores.iter().all(|v| *v == scores[0]) {
+            findings.push(finding(
+                Severity::Info,
+                AnomalyKind::IdenticalRow { value: scores[0], count: scores.len() },
+                row,
+                None,
+            ));
+        }
+    }
+
+    findings.sort_by(|a, b| b.severity.cmp(&a.severity).then(a.row.cmp(&b.row)));
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+
+    #[test]
+    fn test_cell_rules() {
+        let table = fixtures::table(&["Prueba 1", "Prueba 2", "Tarea 1"], &[
+            ("A1", "IMS", &["12/10", "8/10", "4/5"]),
+            ("A2", "IMS", &["9/10", "-2", "3/5"]),
+            ("A3", "IMS", &["7/10", "8/10", "15"]),
+        ]);
+        let findings = detect_anomalies(&table);
+
+        let kinds: Vec<(&AnomalyKind, usize, Option<usize>)> =
+            findings.iter().map(|f| (&f.anomaly, f.row, f.column)).collect();
+        assert!(kinds.contains(&(&AnomalyKind::ObtainedExceedsTotal { obtained: 12.0, total: 10.0 }, 0, Some(0))));
+        assert!(kinds.contains(&(&AnomalyKind::NegativeScore { value: -2.0 }, 1, Some(1))));
+        assert!(kinds.contains(&(&AnomalyKind::AboveEvaluationMax { value: 15.0, max: 5.0 }, 2, Some(2))));
+        assert!(findings.iter().all(|f| f.severity == Severity::Error));
+        assert_eq!(findings[0].carnet, "A1");
+    }
+
+    #[test]
+    fn test_outliers_and_identical_rows() {
+        let table = fixtures::table(&["Prueba 1", "Prueba 2", "Prueba 3"], &[
+            ("A1", "IMS", &["8/10", "7/10", "6/10"]),
+            ("A2", "IMS", &["7/10", "8/10", "8/10"]),
+            ("A3", "IMS", &["8/10", "6/10", "7/10"]),
+            ("A4", "IMS", &["7/10", "7/10", "9/10"]),
+            ("A5", "IMS", &["0/10", "8/10", "7/10"]),
+            ("A6", "IMS", &["8/10", "8/10", "8/10"]),
+        ]);
+        let findings = detect_anomalies(&table);
+
+        let outlier = findings.iter()
+            .find(|f| matches!(f.anomaly, AnomalyKind::ColumnOutlier { .. }))
+            .unwrap();
+        assert_eq!((outlier.row, outlier.column), (4, Some(0)));
+        assert_eq!(outlier.severity, Severity::Warning);
+
+        let identical = findings.last().unwrap();
+        assert_eq!(identical.anomaly, AnomalyKind::IdenticalRow { value: 8.0, count: 3 });
+        assert_eq!((identical.row, identical.column), (5, None));
+    }
+}
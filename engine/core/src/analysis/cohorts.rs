@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::mean;
+use crate::models::carnet::CarnetFormat;
+use crate::models::gradebook::AcademicTable;
+use crate::models::stats::GradeStats;
+use crate::rules::{AcademicStatus, GradingPolicy};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(from_wasm_abi))]
+pub struct CohortOptions {
+    pub format: CarnetFormat,
+    /// Academic year the course runs in (for "years in program").
+    pub current_year: u16,
+    /// Year of the program the course belongs to; students further along are counted as
+    /// repeaters. None skips the repeater split.
+    #[serde(default)]
+    pub course_year: Option<u16>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct CohortStanding {
+    pub student_count: usize,
+    pub average: Option<f32>,
+    pub approved_count: usize,
+    pub failed_count: usize,
+    pub pass_rate: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct CohortSummary {
+    pub admission_year: u16,
+    /// 1 for students admitted this year.
+    pub years_in_program: u16,
+    pub repeater: Option<bool>,
+    pub standing: CohortStanding,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
+pub struct CohortReport {
+    /// Oldest cohort first.
+    pub cohorts: Vec<CohortSummary>,
+    pub first_timers: Option<CohortStanding>,
+    pub repeaters: Option<CohortStanding>,
+    /// Carnets that match none of the patterns.
+    pub invalid_carnets: Vec<String>,
+}
+
+pub fn cohort_report(table: &AcademicTable, policy: &GradingPolicy, options: &CohortOptions) -> CohortReport {
+    let students = GradeStats::with_policy(table, policy).student_summaries();
+
+    // (año de ingreso, acumulado, estado) de cada carnet válido
+    let mut members: Vec<(u16, Option<f32>, AcademicStatus)> = Vec::new();
+    let mut invalid_carnets = Vec::new();
+    for (record, student) in table.records.iter().zip(students) {
+        match options.format.parse(&record.carnet) {
+            Ok(carnet) => members.push((carnet.admission_year, student.accumulated_score, student.status)),
+            Err(_) => invalid_carnets.push(record.carnet.clone()),
+        }
+    }
+
+    let years_in_program = |admission: u16| options.current_year.saturating_sub(admission) + 1;
+    let is_repeater = |admission: u16| options.course_year.map(|course_year| years_in_program(admission) > course_year);
+
+    let mut years: Vec<u16> = members.iter().map(|m| m.0).collect();
+    years.sort_unstable();
+    years.dedup();
+
+    let cohorts = years.into_iter().map(|year| CohortSummary {
+        admission_year: year,
+        years_in_program: years_in_program(year),
+        repeater: is_repeater(year),
+        standing: standing(members.iter().filter(|m| m.0 == year)),
+    }).collect();
+
+    let split = |repeater: bool| {
+        options.course_year.map(|_| standing(members.iter().filter(|m| is_repeater(m.0) == Some(repeater))))
+    };
+
+    CohortReport {
+        cohorts,
+        first_timers: split(false),
+        repeaters: split(true),
+        invalid_carnets,
+    }
+}
+
+fn standing<'a>(members: impl Iterator<Item = &'a (u16, Option<f32>, AcademicStatus)>) -> CohortStanding {
+    let members: Vec<_> = members.collect();
+    let scores: Vec<f32> = members.iter().filter_map(|m| m.1).collect();
+    let approved_count = members.iter().filter(|m| m.2 == AcademicStatus::Approved).count();
+    let failed_count = members.iter().filter(|m| m.2 == AcademicStatus::Failed).count();
+
+    CohortStanding {
+        student_count: members.len(),
+        average: mean(&scores),
+        approved_count,
+        failed_count,
+        pass_rate: (!members.is_empty()).then(|| approved_count as f32 / members.len() as f32),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+
+    #[test]
+    fn test_cohorts_and_repeaters() {
+        let table = fixtures::table(&["Parcial", "Final"], &[
+            ("24-A0101", "IMS", &["45/50", "40/50"]),
+            ("24-A0102", "IMS", &["40/50", "35/50"]),
+            ("23-A0201", "IMS", &["20/50", "25/50"]),
+            ("22-B0007", "ICE", &["30/50", "30/50"]),
+            ("sin carnet", "ICE", &["30/50", "30/50"]),
+        ]);
+        let options = CohortOptions { format: CarnetFormat::default(), current_year: 2024, course_year: Some(1) };
+        let report = cohort_report(&table, &GradingPolicy::default(), &options);
+
+        let years: Vec<u16> = report.cohorts.iter().map(|c| c.admission_year).collect();
+        assert_eq!(years, vec![2022, 2023, 2024]);
+        assert_eq!(report.cohorts[0].years_in_program, 3);
+        assert_eq!(report.cohorts[2].standing.pass_rate, Some(1.0));
+        assert_eq!(report.invalid_carnets, vec!["sin carnet"]);
+
+        let first_timers = report.first_timers.unwrap();
+        let repeaters = report.repeaters.unwrap();
+        assert_eq!((first_timers.student_count, repeaters.student_count), (2, 2));
+        assert_eq!(repeaters.failed_count, 2);
+        assert_eq!(repeaters.average, Some(52.5));
+    }
+}
@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::distributions::{f_upper_tail, student_t_two_tailed};
+use crate::analysis::{mean, variance};
+use crate::models::domain::{ClassSummary, EvaluationSummary};
+use crate::models::gradebook::AcademicTable;
+use crate::models::stats::GradeStats;
+use crate::rules::GradingPolicy;
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct GroupSummary {
+    pub group: String,
+    pub class: ClassSummary,
+    pub evaluations: Vec<EvaluationSummary>,
+}
+
+/// Welch's t-test between the accumulated scores of two groups.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct GroupComparison {
+    pub group_a: String,
+    pub group_b: String,
+    pub mean_difference: Option<f32>, // a - b
+    pub t_statistic: Option<f32>,
+    pub degrees_of_freedom: Option<f32>,
+    pub p_value: Option<f32>,
+    /// Cohen's d with pooled standard deviation.
+    pub cohens_d: Option<f32>,
+}
+
+/// One-way ANOVA of accumulated scores across every group.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct AnovaResult {
+    pub f_statistic: f32,
+    pub df_between: usize,
+    pub df_within: usize,
+    pub p_value: f32,
+    pub eta_squared: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
+pub struct GroupReport {
+    pub groups: Vec<GroupSummary>,
+    pub comparisons: Vec<GroupComparison>,
+    pub anova: Option<AnovaResult>,
+}
+
+/// Summaries and comparisons use the policy's accumulated scores, as the class summary does.
+pub fn group_report(table: &AcademicTable, policy: &GradingPolicy) -> GroupReport {
+    let groups = table.groups();
+
+    let summaries = groups.iter().map(|group| {
+        let sub_table = table.filter_group(group);
+        let stats = GradeStats::with_policy(&sub_table, policy);
+        GroupSummary {
+            group: group.clone(),
+            class: stats.class_summary(),
+            evaluations: stats.evaluation_summaries(),
+        }
+    }).collect();
+
+    // Acumulado de cada estudiante, agrupado por carrera
+    let totals = policy.student_totals(table);
+    let samples: Vec<Vec<f32>> = groups.iter().map(|group| {
+        table.records.iter().zip(&totals)
+            .filter(|(record, _)| record.group == *group)
+            .filter_map(|(_, totals)| totals.accumulated)
+            .collect()
+    }).collect();
+
+    let mut comparisons = Vec::new();
+    for a in 0..groups.len() {
+        for b in (a + 1)..groups.len() {
+            comparisons.push(welch_test(&groups[a], &samples[a], &groups[b], &samples[b]));
+        }
+    }
+
+    GroupReport {
+        groups: summaries,
+        comparisons,
+        anova: one_way_anova(&samples),
+    }
+}
+
+fn welch_test(group_a: &str, a: &[f32], group_b: &str, b: &[f32]) -> GroupComparison {
+    let mean_a = mean(a);
+    let mean_b = mean(b);
+    let mean_difference = mean_a.zip(mean_b).map(|(ma, mb)| ma - mb);
+
+    let mut result = GroupComparison {
+        group_a: group_a.to_string(),
+        group_b: group_b.to_string(),
+        mean_difference,
+        t_statistic: None,
+        degrees_of_freedom: None,
+        p_value: None,
+        cohens_d: None,
+    };
+
+    let (Some(diff), Some(var_a), Some(var_b)) = (mean_difference, variance(a), variance(b)) else {
+        return result;
+    };
+    let (n_a, n_b) = (a.len() as f32, b.len() as f32);
+
+    let pooled_sd = (((n_a - 1.0) * var_a + (n_b - 1.0) * var_b) / (n_a + n_b - 2.0)).sqrt();
+    if pooled_sd > f32::EPSILON {
+        result.cohens_d = Some(diff / pooled_sd);
+    }
+
+    let se_a = var_a / n_a;
+    let se_b = var_b / n_b;
+    let standard_error = (se_a + se_b).sqrt();
+    if standard_error <= f32::EPSILON {
+        return result;
+    }
+
+    let t = diff / standard_error;
+    // Welch–Satterthwaite
+    let df = (se_a + se_b).powi(2) / (se_a.powi(2) / (n_a - 1.0) + se_b.powi(2) / (n_b - 1.0));
+
+    result.t_statistic = Some(t);
+    result.degrees_of_freedom = Some(df);
+    result.p_value = Some(student_t_two_tailed(t as f64, df as f64) as f32);
+    result
+}
+
+fn one_way_anova(samples: &[Vec<f32>]) -> Option<AnovaResult> {
+    let groups: Vec<&Vec<f32>> = samples.iter().filter(|s| !s.is_empty()).collect();
+    let k = groups.len();
+    let n: usize = groups.iter().map(|g| g.len()).sum();
+    if k < 2 || n <= k {
+        return None;
+    }
+
+    let all: Vec<f32> = groups.iter().flat_map(|g| g.iter().copied()).collect();
+    let grand_mean = mean(&all)?;
+
+    let ss_between: f32 = groups.iter()
+        .map(|g| g.len() as f32 * (mean(g).unwrap_or(grand_mean) - grand_mean).powi(2))
+        .sum();
+    let ss_within: f32 = groups.iter()
+        .map(|g| {
+            let m = mean(g).unwrap_or(grand_mean);
+            g.iter().map(|v| (v - m).powi(2)).sum::<f32>()
+        })
+        .sum();
+
+    let df_between = k - 1;
+    let df_within = n - k;
+    if ss_within <= f32::EPSILON {
+        return None;
+    }
+
+    let f_statistic = (ss_between / df_between as f32) / (ss_within / df_within as f32);
+    Some(AnovaResult {
+        f_statistic,
+        df_between,
+        df_within,
+        p_value: f_upper_tail(f_statistic as f64, df_between as f64, df_within as f64) as f32,
+        eta_squared: ss_between / (ss_between + ss_within),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+
+    fn sample_table() -> AcademicTable {
+        fixtures::table(&["Prueba 1", "Prueba 2"], &[
+            ("A1", "IMS", &["30/30", "28/30"]),
+            ("A2", "IMS", &["27/30", "29/30"]),
+            ("A3", "IMS", &["25/30", "26/30"]),
+            ("A4", "ICE", &["10/30", "12/30"]),
+            ("A5", "ICE", &["14/30", "9/30"]),
+            ("A6", "ICE", &["RM", "RM"]),
+        ])
+    }
+
+    #[test]
+    fn test_group_summaries() {
+        let report = group_report(&sample_table(), &GradingPolicy::default());
+
+        assert_eq!(report.groups.len(), 2);
+        let ice = &report.groups[1];
+        assert_eq!(ice.group, "ICE");
+        assert_eq!(ice.class.student_count, 3);
+        assert_eq!(ice.evaluations[0].evaluated_count, 2);
+        assert_eq!(ice.evaluations[0].average, Some(12.0));
+    }
+
+    #[test]
+    fn test_group_comparison() {
+        let report = group_report(&sample_table(), &GradingPolicy::default());
+
+        let cmp = &report.comparisons[0];
+        assert_eq!((cmp.group_a.as_str(), cmp.group_b.as_str()), ("IMS", "ICE"));
+        assert!((cmp.mean_difference.unwrap() - (55.0 - 22.5)).abs() < 1e-4);
+        assert!(cmp.cohens_d.unwrap() > 2.0);
+        assert!(cmp.p_value.unwrap() < 0.05);
+
+        let anova = report.anova.unwrap();
+        assert_eq!((anova.df_between, anova.df_within), (1, 3));
+        assert!(anova.p_value < 0.05);
+        assert!(anova.eta_squared > 0.9);
+    }
+}
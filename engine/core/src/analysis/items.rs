@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{mean, pearson};
+use crate::models::gradebook::AcademicTable;
+use crate::models::stats::{compute_evaluation_max_scores, extract_raw_score, normalized_score};
+use crate::rules::GradingPolicy;
+
+/// Proporción de estudiantes en los grupos superior e inferior (Kelley, 27%).
+const GROUP_FRACTION: f32 = 0.27;
+
+const TOO_EASY_DIFFICULTY: f32 = 0.90;
+const TOO_HARD_DIFFICULTY: f32 = 0.30;
+const LOW_DISCRIMINATION: f32 = 0.20;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub enum ItemFlag {
+    TooEasy,                // Casi todos sacan el máximo
+    TooHard,                // Casi nadie obtiene puntos
+    LowDiscrimination,      // No separa a los fuertes de los débiles
+    NegativeDiscrimination, // Los débiles rinden mejor que los fuertes (¿clave errónea?)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct ItemAnalysis {
+    pub id: String, // Mismo id que EvaluationSummary
+    pub name: String,
+
+    /// Mean proportion of the max score obtained (0 = nobody scored, 1 = everybody got full marks).
+    pub difficulty_index: Option<f32>,
+    /// Upper 27% mean proportion minus lower 27% mean proportion, ranked by accumulated score.
+    pub discrimination_index: Option<f32>,
+    /// Pearson correlation between the item and the accumulated score (point-biserial for 0/1 items).
+    pub item_total_correlation: Option<f32>,
+    /// Same correlation, with the item removed from the total.
+    pub corrected_item_total_correlation: Option<f32>,
+
+    pub group_size: usize,
+    pub flags: Vec<ItemFlag>,
+}
+
+/// Students are ranked by the policy's accumulated score, the same total the dashboard shows.
+pub fn item_analysis(table: &AcademicTable, policy: &GradingPolicy) -> Vec<ItemAnalysis> {
+    let totals: Vec<Option<f32>> = policy.student_totals(table).into_iter().map(|t| t.accumulated).collect();
+    let max_scores = compute_evaluation_max_scores(table);
+
+    table.evaluations.iter().enumerate().map(|(eval_idx, name)| {
+        let column_max = max_scores[eval_idx];
+
+        // (total, raw, normalized) de cada estudiante evaluado en este ítem
+        let mut samples: Vec<(f32, f32, Option<f32>)> = table.records.iter().zip(&totals)
+            .filter_map(|(record, total)| {
+                let grade = record.grades.get(eval_idx)?;
+                let raw = extract_raw_score(grade)?;
+                Some((total.unwrap_or(0.0), raw, normalized_score(grade, column_max)))
+            })
+            .collect();
+
+        let proportions: Vec<f32> = samples.iter().filter_map(|s| s.2).collect();
+        let difficulty_index = mean(&proportions);
+
+        let totals_col: Vec<f32> = samples.iter().map(|s| s.0).collect();
+        let raw_col: Vec<f32> = samples.iter().map(|s| s.1).collect();
+        let rest_col: Vec<f32> = samples.iter().map(|s| s.0 - s.1).collect();
+        let item_total_correlation = pearson(&raw_col, &totals_col);
+        let corrected_item_total_correlation = pearson(&raw_col, &rest_col);
+
+        // Ordenar de mayor a menor acumulado para formar los grupos extremos
+        samples.retain(|s| s.2.is_some());
+        samples.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let group_size = if samples.len() >= 2 {
+            ((samples.len() as f32 * GROUP_FRACTION).round() as usize).clamp(1, samples.len() / 2)
+        } else {
+            0
+        };
+        let upper: Vec<f32> = samples.iter().take(group_size).filter_map(|s| s.2).collect();
+        let lower: Vec<f32> = samples.iter().rev().take(group_size).filter_map(|s| s.2).collect();
+        let discrimination_index = mean(&upper).zip(mean(&lower)).map(|(u, l)| u - l);
+
+        ItemAnalysis {
+            id: eval_idx.to_string(),
+            name: name.clone(),
+            difficulty_index,
+            discrimination_index,
+            item_total_correlation,
+            corrected_item_total_correlation,
+            group_size,
+            flags: item_flags(difficulty_index, discrimination_index),
+        }
+    }).collect()
+}
+
+fn item_flags(difficulty: Option<f32>, discrimination: Option<f32>) -> Vec<ItemFlag> {
+    let mut flags = Vec::new();
+
+    match difficulty {
+        Some(p) if p > TOO_EASY_DIFFICULTY => flags.push(ItemFlag::TooEasy),
+        Some(p) if p < TOO_HARD_DIFFICULTY => flags.push(ItemFlag::TooHard),
+        _ => {}
+    }
+
+    match discrimination {
+        Some(d) if d < 0.0 => flags.push(ItemFlag::NegativeDiscrimination),
+        Some(d) if d < LOW_DISCRIMINATION => flags.push(ItemFlag::LowDiscrimination),
+        _ => {}
+    }
+
+    flags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+    use crate::rules::CategoryRule;
+
+    fn sample_table() -> AcademicTable {
+        // "Prueba" separa bien, "Asistencia" todos 5/5, "Quiz" premia a los más débiles
+        fixtures::table(&["Prueba", "Asistencia", "Quiz"], &[
+            ("A1", "IMS", &["30/30", "5/5", "1/10"]),
+            ("A2", "IMS", &["28/30", "5/5", "2/10"]),
+            ("A3", "IMS", &["20/30", "5/5", "4/10"]),
+            ("A4", "ICE", &["12/30", "5/5", "6/10"]),
+            ("A5", "ICE", &["8/30", "5/5", "8/10"]),
+            ("A6", "ICE", &["4/30", "5/5", "9/10"]),
+        ])
+    }
+
+    #[test]
+    fn test_difficulty_and_discrimination() {
+        let items = item_analysis(&sample_table(), &GradingPolicy::default());
+
+        let prueba = &items[0];
+        assert_eq!(prueba.group_size, 2);
+        assert!((prueba.difficulty_index.unwrap() - 102.0 / 180.0).abs() < 1e-5);
+        // Superior: (30 + 28) / 60, inferior: (8 + 4) / 60
+        assert!((prueba.discrimination_index.unwrap() - 46.0 / 60.0).abs() < 1e-5);
+        assert!(prueba.item_total_correlation.unwrap() > 0.9);
+        assert!(prueba.flags.is_empty());
+
+        let asistencia = &items[1];
+        assert_eq!(asistencia.difficulty_index, Some(1.0));
+        assert_eq!(asistencia.item_total_correlation, None); // Columna constante
+        assert_eq!(asistencia.flags, vec![ItemFlag::TooEasy, ItemFlag::LowDiscrimination]);
+
+        let quiz = &items[2];
+        assert!(quiz.discrimination_index.unwrap() < 0.0);
+        assert!(quiz.corrected_item_total_correlation.unwrap() < 0.0);
+        assert!(quiz.flags.contains(&ItemFlag::NegativeDiscrimination));
+    }
+
+    #[test]
+    fn test_missing_grades_are_excluded() {
+        let table = fixtures::table(&["Prueba"], &[
+            ("A1", "IMS", &["10/10"]),
+            ("A2", "IMS", &["NP"]),
+            ("A3", "IMS", &["RM"]),
+        ]);
+        let items = item_analysis(&table, &GradingPolicy::default());
+
+        assert_eq!(items[0].difficulty_index, Some(1.0));
+        assert_eq!(items[0].discrimination_index, None);
+        assert_eq!(items[0].group_size, 0);
+    }
+
+    #[test]
+    fn test_ranking_follows_the_policy() {
+        // Sin tope, A2 suma 21 y queda arriba; con el tope de 2 puntos en Quiz queda en 3
+        let table = fixtures::table(&["Prueba", "Quiz 1", "Quiz 2"], &[
+            ("A1", "IMS", &["9/10", "0/10", "0/10"]),
+            ("A2", "IMS", &["1/10", "10/10", "10/10"]),
+        ]);
+        let policy = GradingPolicy {
+            categories: vec![CategoryRule { category: "Quiz".to_string(), max_points: Some(2.0), ..Default::default() }],
+            ..GradingPolicy::default()
+        };
+
+        let raw = item_analysis(&table, &GradingPolicy::default());
+        let capped = item_analysis(&table, &policy);
+        assert!((raw[0].discrimination_index.unwrap() + 0.8).abs() < 1e-5);
+        assert!((capped[0].discrimination_index.unwrap() - 0.8).abs() < 1e-5);
+    }
+}
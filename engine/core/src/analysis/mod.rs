@@ -0,0 +1,90 @@
+//! Análisis psicométrico y estadístico sobre un `AcademicTable`.
+//!
+//! Cada submódulo trabaja sobre la tabla ya normalizada y devuelve tipos
+//! serializables, listos para exponerse por WASM.
+
+pub mod items;
+pub mod reliability;
+pub mod groups;
+pub mod projection;
+pub mod anomalies;
+pub mod similarity;
+pub mod cohorts;
+
+pub(crate) mod distributions;
+
+pub(crate) fn mean(values: &[f32]) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f32>() / values.len() as f32)
+}
+
+pub(crate) fn median(values: &[f32]) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        Some((sorted[mid - 1] + sorted[mid]) / 2.0)
+    } else {
+        Some(sorted[mid])
+    }
+}
+
+/// Sample variance (n - 1).
+pub(crate) fn variance(values: &[f32]) -> Option<f32> {
+    if values.len() <= 1 {
+        return None;
+    }
+    let avg = mean(values)?;
+    let sum_sq_diff: f32 = values.iter().map(|v| (v - avg).powi(2)).sum();
+    Some(sum_sq_diff / (values.len() as f32 - 1.0))
+}
+
+/// Fractional ranks (1-based); ties share the average of their positions.
+pub(crate) fn ranks(values: &[f32]) -> Vec<f32> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let mut result = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len() && values[order[end + 1]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end) as f32 / 2.0 + 1.0;
+        for &idx in &order[start..=end] {
+            result[idx] = rank;
+        }
+        start = end + 1;
+    }
+    result
+}
+
+/// Pearson correlation between two paired samples.
+/// Returns None when there are fewer than 3 pairs or either side is constant.
+pub(crate) fn pearson(xs: &[f32], ys: &[f32]) -> Option<f32> {
+    if xs.len() != ys.len() || xs.len() < 3 {
+        return None;
+    }
+    let mx = mean(xs)?;
+    let my = mean(ys)?;
+
+    let mut cov = 0.0;
+    let mut var_x = 0.0;
+    let mut var_y = 0.0;
+    for (x, y) in xs.iter().zip(ys) {
+        cov += (x - mx) * (y - my);
+        var_x += (x - mx).powi(2);
+        var_y += (y - my).powi(2);
+    }
+
+    if var_x <= f32::EPSILON || var_y <= f32::EPSILON {
+        return None;
+    }
+    Some((cov / (var_x * var_y).sqrt()).clamp(-1.0, 1.0))
+}
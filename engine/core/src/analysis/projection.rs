@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::distributions::{student_t_cdf, student_t_critical};
+use crate::analysis::mean;
+use crate::models::gradebook::AcademicTable;
+use crate::models::stats::{compute_evaluation_max_scores, normalized_score};
+use crate::rules::{GradingPolicy, StudentTotals};
+
+/// Mínimo de notas para ajustar una tendencia con residuo.
+const MIN_TREND_POINTS: usize = 3;
+const CONFIDENCE_ALPHA: f64 = 0.05; // Intervalo del 95%
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct StudentProjection {
+    pub id: String,
+    pub name: String,
+
+    /// Change in normalized score per evaluation (least squares slope).
+    pub trend_slope: Option<f32>,
+    /// Proportion of the remaining points the trend expects the student to earn.
+    pub expected_rate: Option<f32>,
+    /// Course points not yet evaluated for this student.
+    pub remaining_points: f32,
+
+    pub projected_final: Option<f32>,
+    pub projected_low: Option<f32>,
+    pub projected_high: Option<f32>,
+    pub pass_probability: Option<f32>,
+}
+
+pub fn project_students(table: &AcademicTable, policy: &GradingPolicy) -> Vec<StudentProjection> {
+    compute_projections(table, policy, &policy.student_totals(table))
+}
+
+pub(crate) fn compute_projections(
+    table: &AcademicTable,
+    policy: &GradingPolicy,
+    totals: &[StudentTotals],
+) -> Vec<StudentProjection> {
+    let max_scores = compute_evaluation_max_scores(table);
+
+    table.records.iter().enumerate().map(|(i, record)| {
+        // Serie (posición de la evaluación, proporción obtenida) en el orden de la hoja
+        let series: Vec<(f32, f32)> = record.grades.iter().enumerate()
+            .filter_map(|(eval_idx, grade)| {
+                normalized_score(grade, max_scores.get(eval_idx).copied().flatten())
+                    .map(|p| (eval_idx as f32, p))
+            })
+            .collect();
+
+        let current = totals[i].accumulated.unwrap_or(0.0);
+        // El crédito extra no ocupa puntos del curso
+        let base = current - totals[i].extra_credit.unwrap_or(0.0);
+        let remaining_points = (policy.total_course_points - base - totals[i].lost.unwrap_or(0.0)).max(0.0);
+
+        let mut projection = StudentProjection {
+            id: record.carnet.clone(),
+            name: record.name.clone(),
+            trend_slope: None,
+            expected_rate: None,
+            remaining_points,
+            projected_final: None,
+            projected_low: None,
+            projected_high: None,
+            pass_probability: None,
+        };
+
+        // Sin puntos en juego el final ya está decidido
+        if remaining_points <= f32::EPSILON {
+            projection.projected_final = Some(current);
+            projection.projected_low = Some(current);
+            projection.projected_high = Some(current);
+            projection.pass_probability = Some(if current >= policy.passing_score { 1.0 } else { 0.0 });
+            return projection;
+        }
+
+        let next_position = table.evaluations.len() as f32;
+        let Some(trend) = fit_trend(&series, next_position) else {
+            return projection;
+        };
+
+        let rate = trend.predicted.clamp(0.0, 1.0);
+        let margin = student_t_critical(CONFIDENCE_ALPHA, trend.df as f64) as f32 * trend.prediction_se;
+
+        projection.trend_slope = Some(trend.slope);
+        projection.expected_rate = Some(rate);
+        projection.projected_final = Some(current + remaining_points * rate);
+        projection.projected_low = Some(current + remaining_points * (rate - margin).clamp(0.0, 1.0));
+        projection.projected_high = Some(current + remaining_points * (rate + margin).clamp(0.0, 1.0));
+        projection.pass_probability = Some(pass_probability(policy.passing_score, current, remaining_points, rate, &trend));
+        projection
+    }).collect()
+}
+
+struct Trend {
+    slope: f32,
+    predicted: f32,
+    prediction_se: f32,
+    df: usize,
+}
+
+/// Ordinary least squares over the series, predicting at `x0`.
+fn fit_trend(series: &[(f32, f32)], x0: f32) -> Option<Trend> {
+    let n = series.len();
+    if n < MIN_TREND_POINTS {
+        return None;
+    }
+
+    let xs: Vec<f32> = series.iter().map(|p| p.0).collect();
+    let ys: Vec<f32> = series.iter().map(|p| p.1).collect();
+    let mx = mean(&xs)?;
+    let my = mean(&ys)?;
+
+    let sxx: f32 = xs.iter().map(|x| (x - mx).powi(2)).sum();
+    let sxy: f32 = series.iter().map(|(x, y)| (x - mx) * (y - my)).sum();
+    if sxx <= f32::EPSILON {
+        return None;
+    }
+
+    let slope = sxy / sxx;
+    let intercept = my - slope * mx;
+    let sse: f32 = series.iter().map(|(x, y)| (y - (intercept + slope * x)).powi(2)).sum();
+    let df = n - 2;
+    let residual_se = (sse / df as f32).sqrt();
+
+    Some(Trend {
+        slope,
+        predicted: intercept + slope * x0,
+        prediction_se: residual_se * (1.0 + 1.0 / n as f32 + (x0 - mx).powi(2) / sxx).sqrt(),
+        df,
+    })
+}
+
+/// P(rate needed to reach the passing score <= rate achieved), with the trend's t distribution.
+fn pass_probability(passing_score: f32, current: f32, remaining_points: f32, rate: f32, trend: &Trend) -> f32 {
+    if current >= passing_score {
+        return 1.0;
+    }
+    let needed_rate = (passing_score - current) / remaining_points;
+    if needed_rate > 1.0 {
+        return 0.0;
+    }
+    if trend.prediction_se <= f32::EPSILON {
+        return if rate >= needed_rate { 1.0 } else { 0.0 };
+    }
+
+    let t = (needed_rate - rate) / trend.prediction_se;
+    (1.0 - student_t_cdf(t as f64, trend.df as f64)) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+
+    #[test]
+    fn test_projection_follows_trend() {
+        let table = fixtures::table(&["Prueba 1", "Prueba 2", "Prueba 3", "Prueba 4"], &[
+            // Rendimiento estable cerca del 90%
+            ("A1", "IMS", &["9/10", "9/10", "18/20", "9/10"]),
+            // Cae en picada: 80%, 60%, 40%, 20%
+            ("A2", "IMS", &["8/10", "6/10", "8/20", "2/10"]),
+            // Sólo dos notas: no hay tendencia
+            ("A3", "IMS", &["5/10", "6/10", "NP", "NP"]),
+        ]);
+        let projections = project_students(&table, &GradingPolicy::default());
+
+        let steady = &projections[0];
+        assert!((steady.remaining_points - 50.0).abs() < 1e-4);
+        assert!(steady.trend_slope.unwrap().abs() < 1e-5);
+        assert!((steady.projected_final.unwrap() - (45.0 + 50.0 * 0.9)).abs() < 1e-3);
+        assert!(steady.pass_probability.unwrap() > 0.99);
+
+        let falling = &projections[1];
+        assert!((falling.trend_slope.unwrap() + 0.2).abs() < 1e-5);
+        // La tendencia llega a 0 en la siguiente evaluación
+        assert_eq!(falling.expected_rate, Some(0.0));
+        assert!(falling.projected_low.unwrap() <= falling.projected_final.unwrap());
+        assert!(falling.pass_probability.unwrap() < 0.01);
+
+        let sparse = &projections[2];
+        assert_eq!(sparse.projected_final, None);
+        assert_eq!(sparse.pass_probability, None);
+    }
+
+    #[test]
+    fn test_finished_course_is_deterministic() {
+        let table = fixtures::table(&["Parcial", "Final"], &[
+            ("A1", "IMS", &["45/50", "40/50"]),
+            ("A2", "IMS", &["30/50", "25/50"]),
+        ]);
+        let projections = project_students(&table, &GradingPolicy::default());
+
+        assert_eq!(projections[0].projected_final, Some(85.0));
+        assert_eq!(projections[0].pass_probability, Some(1.0));
+        assert_eq!(projections[1].pass_probability, Some(0.0));
+    }
+}
@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{pearson, ranks, variance};
+use crate::models::gradebook::AcademicTable;
+use crate::models::stats::extract_raw_score;
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
+pub struct CorrelationMatrix {
+    pub evaluations: Vec<String>,
+    /// `pearson[i][j]` between evaluations i and j, using only students graded in both.
+    pub pearson: Vec<Vec<Option<f32>>>,
+    pub spearman: Vec<Vec<Option<f32>>>,
+    /// Number of students graded in both evaluations.
+    pub pair_counts: Vec<Vec<usize>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct ItemDeletion {
+    pub evaluation_id: String,
+    pub name: String,
+    pub alpha_if_deleted: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct ScaleReliability {
+    pub name: String, // "General" o el nombre de la categoría
+    pub item_count: usize,
+    /// Students graded in every item of the scale (listwise deletion).
+    pub complete_cases: usize,
+    pub cronbach_alpha: Option<f32>,
+    pub items: Vec<ItemDeletion>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
+pub struct ReliabilityReport {
+    pub overall: ScaleReliability,
+    pub categories: Vec<ScaleReliability>,
+}
+
+pub fn correlation_matrix(table: &AcademicTable) -> CorrelationMatrix {
+    let columns = raw_columns(table);
+    let n = columns.len();
+
+    let mut pearson_m = vec![vec![None; n]; n];
+    let mut spearman_m = vec![vec![None; n]; n];
+    let mut pair_counts = vec![vec![0; n]; n];
+
+    for i in 0..n {
+        for j in i..n {
+            // Eliminación por pares: sólo estudiantes con nota en ambas columnas
+            let (xs, ys): (Vec<f32>, Vec<f32>) = columns[i].iter().zip(&columns[j])
+                .filter_map(|(x, y)| x.zip(*y))
+                .unzip();
+
+            let r = pearson(&xs, &ys);
+            let rho = pearson(&ranks(&xs), &ranks(&ys));
+
+            pearson_m[i][j] = r;
+            pearson_m[j][i] = r;
+            spearman_m[i][j] = rho;
+            spearman_m[j][i] = rho;
+            pair_counts[i][j] = xs.len();
+            pair_counts[j][i] = xs.len();
+        }
+    }
+
+    CorrelationMatrix {
+        evaluations: table.evaluations.clone(),
+        pearson: pearson_m,
+        spearman: spearman_m,
+        pair_counts,
+    }
+}
+
+pub fn reliability(table: &AcademicTable) -> ReliabilityReport {
+    let columns = raw_columns(table);
+    let all_items: Vec<usize> = (0..columns.len()).collect();
+
+    // Agrupar por categoría respetando el orden de aparición
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    for (idx, category) in table.evaluation_categories().into_iter().enumerate() {
+        match groups.iter_mut().find(|(name, _)| *name == category) {
+            Some((_, items)) => items.push(idx),
+            None => groups.push((category, vec![idx])),
+        }
+    }
+
+    ReliabilityReport {
+        overall: scale_reliability(table, &columns, "General", &all_items),
+        categories: groups.iter()
+            .filter(|(_, items)| items.len() >= 2)
+            .map(|(name, items)| scale_reliability(table, &columns, name, items))
+            .collect(),
+    }
+}
+
+fn scale_reliability(
+    table: &AcademicTable,
+    columns: &[Vec<Option<f32>>],
+    name: &str,
+    items: &[usize],
+) -> ScaleReliability {
+    let rows = complete_rows(columns, items);
+
+    ScaleReliability {
+        name: name.to_string(),
+        item_count: items.len(),
+        complete_cases: rows.len(),
+        cronbach_alpha: cronbach_alpha(columns, items, &rows),
+        items: items.iter().map(|&deleted| {
+            let remaining: Vec<usize> = items.iter().copied().filter(|&i| i != deleted).collect();
+            ItemDeletion {
+                evaluation_id: deleted.to_string(),
+                name: table.evaluations[deleted].clone(),
+                alpha_if_deleted: cronbach_alpha(columns, &remaining, &complete_rows(columns, &remaining)),
+            }
+        }).collect(),
+    }
+}
+
+/// alpha = k / (k - 1) * (1 - sum(var_i) / var_total)
+fn cronbach_alpha(columns: &[Vec<Option<f32>>], items: &[usize], rows: &[usize]) -> Option<f32> {
+    let k = items.len();
+    if k < 2 || rows.len() < 2 {
+        return None;
+    }
+
+    let item_var_sum: f32 = items.iter()
+        .map(|&i| {
+            let values: Vec<f32> = rows.iter().filter_map(|&r| columns[i][r]).collect();
+            variance(&values)
+        })
+        .sum::<Option<f32>>()?;
+
+    let totals: Vec<f32> = rows.iter()
+        .map(|&r| items.iter().filter_map(|&i| columns[i][r]).sum())
+        .collect();
+    let total_var = variance(&totals)?;
+
+    if total_var <= f32::EPSILON {
+        return None;
+    }
+    Some(k as f32 / (k as f32 - 1.0) * (1.0 - item_var_sum / total_var))
+}
+
+fn complete_rows(columns: &[Vec<Option<f32>>], items: &[usize]) -> Vec<usize> {
+    let row_count = columns.first().map_or(0, Vec::len);
+    (0..row_count)
+        .filter(|&r| items.iter().all(|&i| columns[i][r].is_some()))
+        .collect()
+}
+
+/// Notas crudas por columna: `columns[eval_idx][student_idx]`.
+fn raw_columns(table: &AcademicTable) -> Vec<Vec<Option<f32>>> {
+    (0..table.evaluations.len()).map(|eval_idx| {
+        table.records.iter()
+            .map(|record| record.grades.get(eval_idx).and_then(extract_raw_score))
+            .collect()
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+
+    fn sample_table() -> AcademicTable {
+        fixtures::table(&["Prueba 1", "Prueba 2", "Tarea 1", "Tarea 2"], &[
+            ("A1", "IMS", &["10/10", "9/10", "5/5", "1/5"]),
+            ("A2", "IMS", &["8/10", "8/10", "4/5", "2/5"]),
+            ("A3", "IMS", &["6/10", "5/10", "NP", "3/5"]),
+            ("A4", "ICE", &["4/10", "4/10", "2/5", "4/5"]),
+            ("A5", "ICE", &["2/10", "1/10", "1/5", "5/5"]),
+        ])
+    }
+
+    #[test]
+    fn test_correlation_matrix_pairwise() {
+        let matrix = correlation_matrix(&sample_table());
+
+        assert_eq!(matrix.pair_counts[0][0], 5);
+        assert_eq!(matrix.pair_counts[0][2], 4); // A3 no tiene Tarea 1
+        assert!((matrix.pearson[0][0].unwrap() - 1.0).abs() < 1e-5);
+        assert!(matrix.pearson[0][1].unwrap() > 0.95);
+        assert_eq!(matrix.pearson[0][1], matrix.pearson[1][0]);
+        // Tarea 2 es perfectamente inversa en rango a Prueba 1
+        assert!((matrix.spearman[0][3].unwrap() + 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_cronbach_alpha() {
+        let report = reliability(&sample_table());
+
+        assert_eq!(report.overall.item_count, 4);
+        assert_eq!(report.overall.complete_cases, 4);
+        assert_eq!(report.overall.items.len(), 4);
+
+        let pruebas = report.categories.iter().find(|c| c.name == "Prueba").unwrap();
+        assert_eq!(pruebas.complete_cases, 5);
+        assert!(pruebas.cronbach_alpha.unwrap() > 0.9);
+        // Con sólo 2 ítems no queda escala al eliminar uno
+        assert_eq!(pruebas.items[0].alpha_if_deleted, None);
+
+        // Quitar la tarea inversa mejora la consistencia general
+        let overall_alpha = report.overall.cronbach_alpha.unwrap();
+        let without_inverse = report.overall.items[3].alpha_if_deleted.unwrap();
+        assert!(without_inverse > overall_alpha);
+    }
+}
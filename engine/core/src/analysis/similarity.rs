@@ -0,0 +1,330 @@
+//! Señales de similitud entre estudiantes, como ayuda de triaje para el docente.
+//! Un par marcado no es evidencia de copia: sólo indica qué revisar primero.
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{mean, median};
+use crate::models::gradebook::AcademicTable;
+use crate::models::stats::{compute_evaluation_max_scores, extract_raw_score, normalized_score};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(from_wasm_abi))]
+#[serde(default)]
+pub struct SimilarityOptions {
+    /// Pairs need at least this many evaluations graded for both students.
+    pub min_shared_evaluations: usize,
+    /// Null draws per pair. The smallest reachable p-value is `1 / (permutations + 1)`, so this
+    /// must stay above `1 / max_p_value`. Cost grows with students² × permutations.
+    pub permutations: usize,
+    /// Only pairs with a permutation p-value at or below this are reported.
+    pub max_p_value: f32,
+    pub min_cluster_size: usize,
+    pub seed: u64,
+}
+
+impl Default for SimilarityOptions {
+    fn default() -> Self {
+        Self {
+            min_shared_evaluations: 5,
+            permutations: 500,
+            max_p_value: 0.005,
+            min_cluster_size: 3,
+            seed: 0x504E_4121,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct SharedScore {
+    pub evaluation_idx: usize,
+    pub evaluation: String,
+    pub score: f32,
+    /// Students in the whole class with this exact score.
+    pub frequency: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct SimilarPair {
+    pub carnet_a: String,
+    pub carnet_b: String,
+    pub shared_evaluations: usize,
+    pub identical_scores: usize,
+    /// Mean absolute difference of the double-centered normalized scores.
+    pub distance: f32,
+    pub p_value: f32,
+    /// Identical scores on shared evaluations, rarest first.
+    pub driving_evaluations: Vec<SharedScore>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct ScoreCluster {
+    pub evaluation_idx: usize,
+    pub evaluation: String,
+    pub score: f32,
+    pub carnets: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
+pub struct SimilarityReport {
+    pub pairs: Vec<SimilarPair>,
+    pub clusters: Vec<ScoreCluster>,
+}
+
+pub fn similarity_report(table: &AcademicTable, options: &SimilarityOptions) -> SimilarityReport {
+    SimilarityReport {
+        pairs: similar_pairs(table, options),
+        clusters: score_clusters(table, options),
+    }
+}
+
+fn similar_pairs(table: &AcademicTable, options: &SimilarityOptions) -> Vec<SimilarPair> {
+    let residuals = residual_matrix(table);
+    let raw: Vec<Vec<Option<f32>>> = table.records.iter()
+        .map(|r| (0..table.evaluations.len()).map(|e| r.grades.get(e).and_then(extract_raw_score)).collect())
+        .collect();
+
+    // Valores disponibles por columna, para el muestreo nulo
+    let column_pools: Vec<Vec<(usize, f32)>> = (0..table.evaluations.len()).map(|e| {
+        residuals.iter().enumerate().filter_map(|(s, row)| row[e].map(|v| (s, v))).collect()
+    }).collect();
+
+    let mut rng = SplitMix64(options.seed);
+    let mut pairs = Vec::new();
+
+    for a in 0..residuals.len() {
+        for b in (a + 1)..residuals.len() {
+            let shared: Vec<usize> = (0..table.evaluations.len())
+                .filter(|&e| residuals[a][e].is_some() && residuals[b][e].is_some())
+                .collect();
+            if shared.len() < options.min_shared_evaluations {
+                continue;
+            }
+
+            let observed = pair_distance(&shared, |e| residuals[a][e], |e| residuals[b][e]);
+
+            // Nulo: cada nota de b se reemplaza por la de un tercer compañero al azar en la misma
+            // columna; ni a ni b entran al muestreo
+            let mut at_least_as_close = 0;
+            for _ in 0..options.permutations {
+                let null = pair_distance(&shared, |e| residuals[a][e], |e| {
+                    let pool = &column_pools[e];
+                    // Ambos están en el pool; sin terceros la columna no aporta al nulo
+                    if pool.len() <= 2 {
+                        return None;
+                    }
+                    loop {
+                        let (student, value) = pool[rng.below(pool.len())];
+                        if student != a && student != b {
+                            return Some(value);
+                        }
+                    }
+                });
+                if null <= observed {
+                    at_least_as_close += 1;
+                }
+            }
+            let p_value = (at_least_as_close + 1) as f32 / (options.permutations + 1) as f32;
+            if p_value > options.max_p_value {
+                continue;
+            }
+
+            let mut driving: Vec<SharedScore> = shared.iter()
+                .filter(|&&e| raw[a][e] == raw[b][e])
+                .filter_map(|&e| raw[a][e].map(|score| SharedScore {
+                    evaluation_idx: e,
+                    evaluation: table.evaluations[e].clone(),
+                    score,
+                    frequency: raw.iter().filter(|row| row[e] == Some(score)).count(),
+                }))
+                .collect();
+            driving.sort_by_key(|s| s.frequency);
+
+            pairs.push(SimilarPair {
+                carnet_a: table.records[a].carnet.clone(),
+                carnet_b: table.records[b].carnet.clone(),
+                shared_evaluations: shared.len(),
+                identical_scores: driving.len(),
+                distance: observed,
+                p_value,
+                driving_evaluations: driving,
+            });
+        }
+    }
+
+    pairs.sort_by(|x, y| x.p_value.total_cmp(&y.p_value).then(x.distance.total_cmp(&y.distance)));
+    pairs
+}
+
+fn pair_distance(
+    shared: &[usize],
+    a: impl Fn(usize) -> Option<f32>,
+    mut b: impl FnMut(usize) -> Option<f32>,
+) -> f32 {
+    let diffs: Vec<f32> = shared.iter()
+        .filter_map(|&e| a(e).zip(b(e)).map(|(x, y)| (x - y).abs()))
+        .collect();
+    mean(&diffs).unwrap_or(f32::INFINITY)
+}
+
+/// Normalized scores with the student's mean and the evaluation's mean removed, so that two
+/// strong students are not similar just for being strong.
+fn residual_matrix(table: &AcademicTable) -> Vec<Vec<Option<f32>>> {
+    let max_scores = compute_evaluation_max_scores(table);
+    let normalized: Vec<Vec<Option<f32>>> = table.records.iter().map(|record| {
+        (0..table.evaluations.len())
+            .map(|e| record.grades.get(e).and_then(|g| normalized_score(g, max_scores[e])))
+            .collect()
+    }).collect();
+
+    let column_means: Vec<f32> = (0..table.evaluations.len()).map(|e| {
+        let values: Vec<f32> = normalized.iter().filter_map(|row| row[e]).collect();
+        mean(&values).unwrap_or(0.0)
+    }).collect();
+
+    normalized.iter().map(|row| {
+        let centered: Vec<Option<f32>> = row.iter().zip(&column_means).map(|(v, m)| v.map(|v| v - m)).collect();
+        let present: Vec<f32> = centered.iter().flatten().copied().collect();
+        let row_mean = mean(&present).unwrap_or(0.0);
+        centered.iter().map(|v| v.map(|v| v - row_mean)).collect()
+    }).collect()
+}
+
+/// Groups of students sharing the same score on one evaluation, when that score is neither zero,
+/// full marks, nor within one MAD of the column median (where ties are expected).
+fn score_clusters(table: &AcademicTable, options: &SimilarityOptions) -> Vec<ScoreCluster> {
+    let max_scores = compute_evaluation_max_scores(table);
+    let mut clusters = Vec::new();
+
+    for (e, name) in table.evaluations.iter().enumerate() {
+        let scores: Vec<(usize, f32)> = table.records.iter().enumerate()
+            .filter_map(|(s, r)| r.grades.get(e).and_then(extract_raw_score).map(|v| (s, v)))
+            .collect();
+        let values: Vec<f32> = scores.iter().map(|s| s.1).collect();
+        let Some(med) = median(&values) else { continue };
+        let deviations: Vec<f32> = values.iter().map(|v| (v - med).abs()).collect();
+        let mad = median(&deviations).unwrap_or(0.0);
+
+        let mut distinct: Vec<f32> = values.clone();
+        distinct.sort_by(f32::total_cmp);
+        distinct.dedup();
+
+        for score in distinct {
+            let is_boundary = score == 0.0 || max_scores[e] == Some(score);
+            if is_boundary || (score - med).abs() <= mad {
+                continue;
+            }
+            let carnets: Vec<String> = scores.iter()
+                .filter(|(_, v)| *v == score)
+                .map(|(s, _)| table.records[*s].carnet.clone())
+                .collect();
+            if carnets.len() >= options.min_cluster_size {
+                clusters.push(ScoreCluster { evaluation_idx: e, evaluation: name.clone(), score, carnets });
+            }
+        }
+    }
+
+    clusters
+}
+
+/// Generador determinista: el mismo `seed` produce el mismo reporte.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+
+    fn sample_table() -> AcademicTable {
+        fixtures::table(&["P1", "P2", "P3", "P4", "P5", "P6"], &[
+            // A1 y A2 comparten exactamente el mismo patrón irregular
+            ("A1", "IMS", &["3/10", "9/10", "2/10", "8/10", "4/10", "9/10"]),
+            ("A2", "IMS", &["3/10", "9/10", "2/10", "8/10", "4/10", "9/10"]),
+            ("A3", "IMS", &["7/10", "6/10", "7/10", "6/10", "8/10", "5/10"]),
+            ("A4", "IMS", &["6/10", "5/10", "8/10", "7/10", "6/10", "6/10"]),
+            ("A5", "ICE", &["8/10", "7/10", "6/10", "5/10", "7/10", "7/10"]),
+            ("A6", "ICE", &["5/10", "8/10", "9/10", "7/10", "5/10", "6/10"]),
+            ("A7", "ICE", &["9/10", "6/10", "5/10", "6/10", "9/10", "8/10"]),
+            ("A8", "ICE", &["4/10", "8/10", "6/10", "9/10", "5/10", "7/10"]),
+        ])
+    }
+
+    #[test]
+    fn test_identical_pattern_is_flagged() {
+        let report = similarity_report(&sample_table(), &SimilarityOptions::default());
+
+        assert_eq!(report.pairs.len(), 1);
+        let pair = &report.pairs[0];
+        assert_eq!((pair.carnet_a.as_str(), pair.carnet_b.as_str()), ("A1", "A2"));
+        assert_eq!(pair.distance, 0.0);
+        assert_eq!(pair.identical_scores, 6);
+        // La nota más rara de la pareja aparece primero
+        assert_eq!(pair.driving_evaluations[0].frequency, 2);
+    }
+
+    #[test]
+    fn test_report_is_deterministic() {
+        let options = SimilarityOptions { max_p_value: 1.0, ..SimilarityOptions::default() };
+        let first = similarity_report(&sample_table(), &options);
+        let second = similarity_report(&sample_table(), &options);
+
+        let p_values = |r: &SimilarityReport| r.pairs.iter().map(|p| p.p_value).collect::<Vec<_>>();
+        assert_eq!(p_values(&first), p_values(&second));
+        assert_eq!(first.pairs.len(), 28);
+    }
+
+    #[test]
+    fn test_null_never_draws_the_pair_itself() {
+        // Sólo A1 y A2 tienen nota en P1: sin terceros, P1 no entra al nulo y el resto decide
+        let table = fixtures::table(&["P1", "P2"], &[
+            ("A1", "IMS", &["3/10", "3/10"]),
+            ("A2", "IMS", &["3/10", "3/10"]),
+            ("A3", "IMS", &["NP", "9/10"]),
+        ]);
+        let options = SimilarityOptions { min_shared_evaluations: 2, max_p_value: 1.0, ..SimilarityOptions::default() };
+        let report = similarity_report(&table, &options);
+
+        let pair = report.pairs.iter().find(|p| p.carnet_a == "A1" && p.carnet_b == "A2").unwrap();
+        // Con el propio b en el nulo, las copias exactas de su nota subirían el p-value
+        assert_eq!(pair.p_value, 1.0 / (options.permutations + 1) as f32);
+    }
+
+    #[test]
+    fn test_unusual_score_clusters() {
+        let table = fixtures::table(&["Prueba"], &[
+            ("A1", "IMS", &["2/10"]),
+            ("A2", "IMS", &["2/10"]),
+            ("A3", "IMS", &["2/10"]),
+            ("A4", "IMS", &["7/10"]),
+            ("A5", "IMS", &["8/10"]),
+            ("A6", "IMS", &["7/10"]),
+            ("A7", "IMS", &["8/10"]),
+            ("A8", "IMS", &["10/10"]),
+            ("A9", "IMS", &["10/10"]),
+            ("A10", "IMS", &["10/10"]),
+        ]);
+        let report = similarity_report(&table, &SimilarityOptions::default());
+
+        // 10/10 es nota máxima: no se marca aunque la compartan tres
+        assert_eq!(report.clusters.len(), 1);
+        assert_eq!(report.clusters[0].score, 2.0);
+        assert_eq!(report.clusters[0].carnets, vec!["A1", "A2", "A3"]);
+    }
+}
@@ -0,0 +1,360 @@
+//! Reporte de fin de ciclo sobre muchos libros a la vez (un departamento completo).
+//!
+//! Cada archivo se resume por separado con `item`, que no comparte estado y puede correr en
+//! paralelo; `report` junta los resultados y los errores.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{mean, variance};
+use crate::models::attendance::AttendanceSheet;
+use crate::models::gradebook::AcademicTable;
+use crate::models::stats::GradeStats;
+use crate::rules::{AcademicStatus, GradingPolicy};
+
+/// How far (in standard deviations of the other gradebooks) a failure rate must be to stand out.
+const UNUSUAL_Z: f32 = 2.0;
+/// Fewer gradebooks to compare against than this and nothing is flagged.
+const MIN_COMPARED: usize = 3;
+/// Floor for the spread of failure rates, so near-identical courses do not flag tiny differences.
+const MIN_SPREAD: f32 = 0.05;
+
+/// Curso, sección y docente, leídos de los encabezados del libro.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GradebookInfo {
+    pub course: String,
+    pub section: Option<String>,
+    pub teacher: Option<String>,
+}
+
+impl GradebookInfo {
+    /// Busca "Docente: …", "Sección …" y "Asignatura: …" (o variantes) en las celdas de los
+    /// encabezados; sin asignatura explícita usa la primera celda sin etiqueta, y si no hay
+    /// ninguna `fallback` (normalmente el nombre del archivo).
+    pub fn from_headers(description_headers: &[String], fallback: &str) -> Self {
+        let mut info = Self::default();
+        let cells: Vec<&str> = description_headers.iter()
+            .flat_map(|line| line.split(" | "))
+            .map(str::trim)
+            .filter(|cell| !cell.is_empty())
+            .collect();
+
+        let mut unlabeled = Vec::new();
+        for cell in &cells {
+            let lower = cell.to_lowercase();
+            let value = || {
+                let value = cell.split_once(':').map(|(_, v)| v).unwrap_or_else(|| {
+                    cell.split_once(char::is_whitespace).map(|(_, v)| v).unwrap_or("")
+                });
+                Some(value.trim().to_string()).filter(|v| !v.is_empty())
+            };
+            if ["docente", "profesor", "catedrático", "catedratico", "teacher"].iter().any(|k| lower.starts_with(k)) {
+                info.teacher = info.teacher.take().or_else(value);
+            } else if ["sección", "seccion", "section"].iter().any(|k| lower.starts_with(k)) {
+                info.section = info.section.take().or_else(value);
+            } else if ["asignatura", "materia", "curso", "course"].iter().any(|k| lower.starts_with(k)) {
+                if info.course.is_empty() {
+                    info.course = value().unwrap_or_default();
+                }
+            } else {
+                unlabeled.push(*cell);
+            }
+        }
+
+        if info.course.is_empty() {
+            info.course = unlabeled.first().map(|cell| cell.to_string()).unwrap_or_else(|| fallback.to_string());
+        }
+        info
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudentStanding {
+    pub carnet: String,
+    pub name: String,
+    pub accumulated_score: Option<f32>,
+    pub status: AcademicStatus,
+}
+
+/// Un libro ya procesado.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchItem {
+    pub file: String,
+    pub info: GradebookInfo,
+    pub students: Vec<StudentStanding>,
+}
+
+impl BatchItem {
+    fn count(&self, status: AcademicStatus) -> usize {
+        self.students.iter().filter(|s| s.status == status).count()
+    }
+
+    fn failure_rate(&self) -> Option<f32> {
+        (!self.students.is_empty()).then(|| self.count(AcademicStatus::Failed) as f32 / self.students.len() as f32)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchError {
+    pub file: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PassRate {
+    /// Course, section or teacher name, or the file for one gradebook.
+    pub key: String,
+    pub gradebook_count: usize,
+    pub student_count: usize,
+    pub approved_count: usize,
+    pub at_risk_count: usize, // Warning o Critical
+    pub failed_count: usize,
+    pub pass_rate: Option<f32>,
+    pub failure_rate: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnusualFailureRate {
+    pub file: String,
+    pub info: GradebookInfo,
+    pub failure_rate: f32,
+    /// Mean failure rate of the other gradebooks.
+    pub others_mean: f32,
+    /// Positive when this gradebook fails more students than the others.
+    pub z_score: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CourseStanding {
+    pub file: String,
+    pub course: String,
+    pub section: Option<String>,
+    pub accumulated_score: Option<f32>,
+    pub status: AcademicStatus,
+}
+
+/// Estudiante en riesgo en al menos un curso.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudentRisk {
+    pub carnet: String,
+    pub name: String,
+    pub failed_count: usize,
+    pub at_risk_count: usize,
+    /// Only the courses where the student is at risk or failed.
+    pub courses: Vec<CourseStanding>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchReport {
+    pub gradebooks: Vec<PassRate>,
+    pub by_course: Vec<PassRate>,
+    pub by_section: Vec<PassRate>,
+    pub by_teacher: Vec<PassRate>,
+    pub unusual_failure_rates: Vec<UnusualFailureRate>,
+    /// Most failed courses first.
+    pub at_risk_students: Vec<StudentRisk>,
+    pub errors: Vec<BatchError>,
+}
+
+/// Resultados de un libro ya cargado. `file` sólo identifica el libro en el reporte.
+pub fn item(file: &str, info: GradebookInfo, table: &AcademicTable, policy: &GradingPolicy, attendance: Option<&AttendanceSheet>) -> BatchItem {
+    let summaries = GradeStats::with_attendance(table, policy, attendance).student_summaries();
+    let students = table.records.iter().zip(summaries).map(|(record, summary)| StudentStanding {
+        carnet: record.carnet.trim().to_string(),
+        name: record.name.clone(),
+        accumulated_score: summary.accumulated_score,
+        status: summary.status,
+    }).collect();
+    BatchItem { file: file.to_string(), info, students }
+}
+
+pub fn report(mut items: Vec<BatchItem>, errors: Vec<BatchError>) -> BatchReport {
+    items.sort_by(|a, b| a.file.cmp(&b.file));
+
+    let gradebooks = items.iter().map(|item| pass_rate(item.file.clone(), &[item])).collect();
+    let by_course = grouped(&items, |item| Some(item.info.course.clone()));
+    let by_section = grouped(&items, |item| {
+        item.info.section.as_ref().map(|section| format!("{} | {}", item.info.course, section))
+    });
+    let by_teacher = grouped(&items, |item| item.info.teacher.clone());
+
+    BatchReport {
+        gradebooks,
+        by_course,
+        by_section,
+        by_teacher,
+        unusual_failure_rates: unusual_failure_rates(&items),
+        at_risk_students: at_risk_students(&items),
+        errors,
+    }
+}
+
+fn pass_rate(key: String, items: &[&BatchItem]) -> PassRate {
+    let student_count: usize = items.iter().map(|i| i.students.len()).sum();
+    let count = |status: AcademicStatus| items.iter().map(|i| i.count(status.clone())).sum::<usize>();
+    let (approved_count, failed_count) = (count(AcademicStatus::Approved), count(AcademicStatus::Failed));
+    let rate = |n: usize| (student_count > 0).then(|| n as f32 / student_count as f32);
+
+    PassRate {
+        key,
+        gradebook_count: items.len(),
+        student_count,
+        approved_count,
+        at_risk_count: count(AcademicStatus::Warning) + count(AcademicStatus::Critical),
+        failed_count,
+        pass_rate: rate(approved_count),
+        failure_rate: rate(failed_count),
+    }
+}
+
+/// Tasas por clave, en orden alfabético; los libros sin clave no cuentan.
+fn grouped(items: &[BatchItem], key: impl Fn(&BatchItem) -> Option<String>) -> Vec<PassRate> {
+    let mut keys: Vec<String> = items.iter().filter_map(&key).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter().map(|k| {
+        let members: Vec<&BatchItem> = items.iter().filter(|item| key(item).as_ref() == Some(&k)).collect();
+        pass_rate(k, &members)
+    }).collect()
+}
+
+/// Compara cada libro con el resto: incluirlo en su propio promedio lo esconde cuando son pocos.
+fn unusual_failure_rates(items: &[BatchItem]) -> Vec<UnusualFailureRate> {
+    let rates: Vec<(usize, f32)> = items.iter().enumerate()
+        .filter_map(|(i, item)| item.failure_rate().map(|rate| (i, rate)))
+        .collect();
+
+    let mut unusual: Vec<UnusualFailureRate> = rates.iter().filter_map(|&(i, rate)| {
+        let others: Vec<f32> = rates.iter().filter(|(j, _)| *j != i).map(|(_, r)| *r).collect();
+        if others.len() < MIN_COMPARED {
+            return None;
+        }
+        let others_mean = mean(&others)?;
+        let spread = variance(&others).map(f32::sqrt).unwrap_or(0.0).max(MIN_SPREAD);
+        let z_score = (rate - others_mean) / spread;
+        (z_score.abs() >= UNUSUAL_Z).then(|| UnusualFailureRate {
+            file: items[i].file.clone(),
+            info: items[i].info.clone(),
+            failure_rate: rate,
+            others_mean,
+            z_score,
+        })
+    }).collect();
+    unusual.sort_by(|a, b| b.z_score.total_cmp(&a.z_score));
+    unusual
+}
+
+fn at_risk_students(items: &[BatchItem]) -> Vec<StudentRisk> {
+    let mut students: Vec<StudentRisk> = Vec::new();
+    // Posición en `students` por carnet en mayúsculas
+    let mut index_of: HashMap<String, usize> = HashMap::new();
+    for item in items {
+        for student in &item.students {
+            let failed = student.status == AcademicStatus::Failed;
+            let at_risk = matches!(student.status, AcademicStatus::Warning | AcademicStatus::Critical);
+            if !(failed || at_risk) || student.carnet.is_empty() {
+                continue;
+            }
+
+            let index = *index_of.entry(student.carnet.to_ascii_uppercase()).or_insert_with(|| {
+                students.push(StudentRisk {
+                    carnet: student.carnet.clone(),
+                    name: student.name.clone(),
+                    failed_count: 0,
+                    at_risk_count: 0,
+                    courses: Vec::new(),
+                });
+                students.len() - 1
+            });
+            let entry = &mut students[index];
+            entry.failed_count += usize::from(failed);
+            entry.at_risk_count += usize::from(at_risk);
+            entry.courses.push(CourseStanding {
+                file: item.file.clone(),
+                course: item.info.course.clone(),
+                section: item.info.section.clone(),
+                accumulated_score: student.accumulated_score,
+                status: student.status.clone(),
+            });
+        }
+    }
+
+    students.sort_by(|a, b| {
+        (b.failed_count, b.at_risk_count).cmp(&(a.failed_count, a.at_risk_count)).then_with(|| a.carnet.cmp(&b.carnet))
+    });
+    students
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+
+    fn info(course: &str, section: &str, teacher: &str) -> GradebookInfo {
+        GradebookInfo { course: course.to_string(), section: Some(section.to_string()), teacher: Some(teacher.to_string()) }
+    }
+
+    fn book(file: &str, info: GradebookInfo, rows: &[(&str, &str, &[&str])]) -> BatchItem {
+        let table = fixtures::table(&["Parcial", "Final"], rows);
+        item(file, info, &table, &GradingPolicy::default(), None)
+    }
+
+    #[test]
+    fn test_info_from_headers() {
+        let headers = vec![
+            "Cálculo I | Ciclo 02-2024".to_string(),
+            "Sección B | Docente: María López".to_string(),
+        ];
+        let info = GradebookInfo::from_headers(&headers, "archivo");
+        assert_eq!(info.course, "Cálculo I");
+        assert_eq!(info.section.as_deref(), Some("B"));
+        assert_eq!(info.teacher.as_deref(), Some("María López"));
+
+        let explicit = GradebookInfo::from_headers(&["Asignatura: Física | Sección: 02".to_string()], "archivo");
+        assert_eq!((explicit.course.as_str(), explicit.section.as_deref()), ("Física", Some("02")));
+        assert_eq!(GradebookInfo::from_headers(&[], "notas").course, "notas");
+    }
+
+    #[test]
+    fn test_aggregate_report() {
+        let passing: &[(&str, &str, &[&str])] = &[("A1", "IMS", &["45/50", "40/50"]), ("A2", "IMS", &["40/50", "35/50"])];
+        let items = vec![
+            book("calculo_a.xlsx", info("Cálculo", "A", "López"), passing),
+            book("calculo_b.xlsx", info("Cálculo", "B", "Ruiz"), &[("B1", "IMS", &["10/50", "5/50"]), ("A1", "IMS", &["45/50", "40/50"])]),
+            book("fisica_a.xlsx", info("Física", "A", "López"), passing),
+            book("fisica_b.xlsx", info("Física", "B", "López"), passing),
+            book("quimica.xlsx", info("Química", "A", "Ruiz"), &[("C1", "ICE", &["10/50", "5/50"]), ("C2", "ICE", &["5/50", "2/50"])]),
+        ];
+        let errors = vec![BatchError { file: "roto.xlsx".to_string(), message: "Error processing Excel file".to_string() }];
+        let report = report(items, errors);
+
+        assert_eq!(report.gradebooks.len(), 5);
+        let calculo = &report.by_course[0];
+        assert_eq!((calculo.key.as_str(), calculo.gradebook_count, calculo.student_count), ("Cálculo", 2, 4));
+        assert_eq!(calculo.failure_rate, Some(0.25));
+        assert_eq!(report.by_section.len(), 5);
+        assert_eq!(report.by_teacher.iter().map(|t| t.key.as_str()).collect::<Vec<_>>(), vec!["López", "Ruiz"]);
+
+        // Química reprueba a todos; los demás a la mitad o a nadie
+        assert_eq!(report.unusual_failure_rates.len(), 1);
+        assert_eq!(report.unusual_failure_rates[0].file, "quimica.xlsx");
+
+        let risk: Vec<&str> = report.at_risk_students.iter().map(|s| s.carnet.as_str()).collect();
+        assert_eq!(risk, vec!["B1", "C1", "C2"]);
+        assert_eq!(report.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_at_risk_students_match_carnets_ignoring_case() {
+        let failing: &[&str] = &["10/50", "5/50"];
+        let items = vec![
+            book("calculo.xlsx", info("Cálculo", "A", "López"), &[("ab1", "IMS", failing)]),
+            book("fisica.xlsx", info("Física", "A", "López"), &[("AB1", "IMS", failing), ("C1", "IMS", failing)]),
+        ];
+        let report = report(items, Vec::new());
+
+        assert_eq!(report.at_risk_students.len(), 2);
+        assert_eq!((report.at_risk_students[0].carnet.as_str(), report.at_risk_students[0].failed_count), ("ab1", 2));
+    }
+}
@@ -0,0 +1,279 @@
+//! Qué cambió entre dos descargas del mismo libro de notas.
+
+use serde::{Deserialize, Serialize};
+
+use crate::merge::{normalize, same_numbers, similarity};
+use crate::models::gradebook::{AcademicTable, GradeValue};
+use crate::models::stats::GradeStats;
+use crate::rules::{AcademicStatus, GradingPolicy};
+use crate::transform::CellChange;
+
+/// Lowest name similarity for a removed and an added evaluation to count as a rename.
+const RENAME_SIMILARITY: f32 = 0.8;
+/// Share of identical cells that makes two differently named columns the same evaluation.
+const RENAME_SAME_VALUES: f32 = 0.8;
+/// Graded cells needed before the content of two columns is compared at all.
+const RENAME_MIN_GRADED: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct StudentRef {
+    pub carnet: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct EvaluationRename {
+    pub old_name: String,
+    pub new_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct StatusChange {
+    pub carnet: String,
+    pub old_status: AcademicStatus,
+    pub new_status: AcademicStatus,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
+pub struct GradebookDiff {
+    pub added_students: Vec<StudentRef>,
+    pub removed_students: Vec<StudentRef>,
+    pub added_evaluations: Vec<String>,
+    pub removed_evaluations: Vec<String>,
+    pub renamed_evaluations: Vec<EvaluationRename>,
+    /// Cells of students and evaluations present in both versions; row and column refer to `new`.
+    pub changed_cells: Vec<CellChange>,
+    pub status_changes: Vec<StatusChange>,
+}
+
+impl GradebookDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_students.is_empty()
+            && self.removed_students.is_empty()
+            && self.added_evaluations.is_empty()
+            && self.removed_evaluations.is_empty()
+            && self.renamed_evaluations.is_empty()
+            && self.changed_cells.is_empty()
+            && self.status_changes.is_empty()
+    }
+}
+
+pub fn diff(old: &AcademicTable, new: &AcademicTable) -> GradebookDiff {
+    diff_with_policy(old, new, &GradingPolicy::default())
+}
+
+/// Igual que `diff`, con los estados calculados según `policy`.
+pub fn diff_with_policy(old: &AcademicTable, new: &AcademicTable, policy: &GradingPolicy) -> GradebookDiff {
+    diff_with_stats(&GradeStats::with_policy(old, policy), &GradeStats::with_policy(new, policy))
+}
+
+/// Igual que `diff`, con los estados de cada versión tal como los dan sus estadísticas
+/// (reglamento, asistencia y reposición incluidos).
+pub fn diff_with_stats(old_stats: &GradeStats, new_stats: &GradeStats) -> GradebookDiff {
+    let (old, new) = (old_stats.table(), new_stats.table());
+    let mut result = GradebookDiff::default();
+
+    // Estudiantes por carnet: (fila vieja, fila nueva)
+    let find = |table: &AcademicTable, carnet: &str| table.records.iter().position(|r| r.carnet.trim() == carnet.trim());
+    let common_rows: Vec<(usize, usize)> = new.records.iter().enumerate()
+        .filter_map(|(new_row, r)| find(old, &r.carnet).map(|old_row| (old_row, new_row)))
+        .collect();
+    result.added_students = new.records.iter()
+        .filter(|r| find(old, &r.carnet).is_none())
+        .map(|r| StudentRef { carnet: r.carnet.clone(), name: r.name.clone() })
+        .collect();
+    result.removed_students = old.records.iter()
+        .filter(|r| find(new, &r.carnet).is_none())
+        .map(|r| StudentRef { carnet: r.carnet.clone(), name: r.name.clone() })
+        .collect();
+
+    let columns = match_columns(old, new, &common_rows, &mut result);
+
+    for &(old_row, new_row) in &common_rows {
+        let (before, after) = (&old.records[old_row], &new.records[new_row]);
+        for &(old_col, new_col) in &columns {
+            let (Some(old_value), Some(new_value)) = (before.grades.get(old_col), after.grades.get(new_col)) else {
+                continue;
+            };
+            if old_value != new_value {
+                result.changed_cells.push(CellChange {
+                    row: new_row,
+                    column: new_col,
+                    carnet: after.carnet.clone(),
+                    old_value: old_value.clone(),
+                    new_value: new_value.clone(),
+                });
+            }
+        }
+    }
+
+    for &(old_row, new_row) in &common_rows {
+        let (old_status, new_status) = (old_stats.academic_status(old_row), new_stats.academic_status(new_row));
+        if old_status != new_status {
+            result.status_changes.push(StatusChange {
+                carnet: new.records[new_row].carnet.clone(),
+                old_status,
+                new_status,
+            });
+        }
+    }
+
+    result
+}
+
+/// Pares (columna vieja, columna nueva): primero por nombre, luego renombres por parecido del
+/// nombre o por contenido casi idéntico. Lo que sobra queda como agregado o eliminado.
+fn match_columns(old: &AcademicTable, new: &AcademicTable, common_rows: &[(usize, usize)], result: &mut GradebookDiff) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    let mut new_used = vec![false; new.evaluations.len()];
+    let mut unmatched_old = Vec::new();
+
+    for (old_col, name) in old.evaluations.iter().enumerate() {
+        let key = normalize(name);
+        match (0..new.evaluations.len()).find(|&c| !new_used[c] && normalize(&new.evaluations[c]) == key) {
+            Some(new_col) => {
+                new_used[new_col] = true;
+                pairs.push((old_col, new_col));
+            }
+            None => unmatched_old.push(old_col),
+        }
+    }
+
+    // Sólo cuentan las filas con nota en alguno de los dos lados: dos columnas vacías no son la misma
+    let graded = |value: Option<&GradeValue>| matches!(value, Some(GradeValue::Numeric(_) | GradeValue::Fraction { .. }));
+    let same_values = |old_col: usize, new_col: usize| {
+        let (mut rows, mut equal) = (0, 0);
+        for &(o, n) in common_rows {
+            let (before, after) = (old.records[o].grades.get(old_col), new.records[n].grades.get(new_col));
+            if graded(before) || graded(after) {
+                rows += 1;
+                equal += usize::from(before == after);
+            }
+        }
+        if rows < RENAME_MIN_GRADED {
+            return 0.0;
+        }
+        equal as f32 / rows as f32
+    };
+
+    for old_col in unmatched_old {
+        let old_name = &old.evaluations[old_col];
+        let renamed = (0..new.evaluations.len())
+            .filter(|&c| !new_used[c])
+            .find(|&c| {
+                let new_name = &new.evaluations[c];
+                (same_numbers(old_name, new_name) && similarity(&normalize(old_name), &normalize(new_name)) >= RENAME_SIMILARITY)
+                    || same_values(old_col, c) >= RENAME_SAME_VALUES
+            });
+
+        match renamed {
+            Some(new_col) => {
+                new_used[new_col] = true;
+                pairs.push((old_col, new_col));
+                result.renamed_evaluations.push(EvaluationRename {
+                    old_name: old_name.clone(),
+                    new_name: new.evaluations[new_col].clone(),
+                });
+            }
+            None => result.removed_evaluations.push(old_name.clone()),
+        }
+    }
+
+    result.added_evaluations = new.evaluations.iter().enumerate()
+        .filter(|(c, _)| !new_used[*c])
+        .map(|(_, name)| name.clone())
+        .collect();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::attendance::{AttendancePolicy, AttendanceSheet};
+    use crate::models::gradebook::fixtures;
+    use crate::parser::parse_csv;
+
+    #[test]
+    fn test_diff_between_uploads() {
+        let old = fixtures::table(&["Prueba 1", "Tarea", "Examen"], &[
+            ("A1", "IMS", &["18/20", "9/10", "NP"]),
+            ("A2", "IMS", &["10/20", "4/10", "NP"]),
+            ("A3", "IMS", &["15/20", "8/10", "NP"]),
+            ("A5", "IMS", &["14/20", "7/10", "NP"]),
+        ]);
+        let new = fixtures::table(&["Prueba 1", "Tarea 1", "Examen Parcial", "Proyecto"], &[
+            ("A1", "IMS", &["18/20", "9/10", "40/50", "8/20"]),
+            ("A2", "IMS", &["12/20", "4/10", "5/50", "2/20"]),
+            ("A4", "ICE", &["16/20", "7/10", "30/50", "NP"]),
+            ("A5", "IMS", &["14/20", "7/10", "NP", "NP"]),
+        ]);
+        let result = diff(&old, &new);
+
+        assert_eq!(result.added_students[0].carnet, "A4");
+        assert_eq!(result.removed_students[0].carnet, "A3");
+
+        // "Tarea" → "Tarea 1" por contenido; "Examen" → "Examen Parcial" no se parece lo suficiente
+        assert_eq!(result.renamed_evaluations.len(), 1);
+        assert_eq!(result.renamed_evaluations[0].new_name, "Tarea 1");
+        assert_eq!(result.removed_evaluations, vec!["Examen"]);
+        assert_eq!(result.added_evaluations, vec!["Examen Parcial", "Proyecto"]);
+
+        assert_eq!(result.changed_cells.len(), 1);
+        assert_eq!(result.changed_cells[0].carnet, "A2");
+        assert_eq!(result.changed_cells[0].new_value, GradeValue::Fraction { obtained: 12.0, total: 20.0 });
+
+        let a1 = result.status_changes.iter().find(|c| c.carnet == "A1").unwrap();
+        assert_eq!(a1.new_status, AcademicStatus::Approved);
+        let a2 = result.status_changes.iter().find(|c| c.carnet == "A2").unwrap();
+        assert_eq!(a2.new_status, AcademicStatus::Failed);
+    }
+
+    #[test]
+    fn test_identical_tables() {
+        let table = fixtures::table(&["Prueba 1"], &[("A1", "IMS", &["18/20"])]);
+        assert!(diff(&table, &table).is_empty());
+    }
+
+    #[test]
+    fn test_empty_columns_are_not_renames() {
+        let old = fixtures::table(&["Tarea", "Laboratorio"], &[
+            ("A1", "IMS", &["9/10", "NP"]),
+            ("A2", "IMS", &["4/10", "NP"]),
+            ("A3", "IMS", &["8/10", "NP"]),
+        ]);
+        let new = fixtures::table(&["Tarea", "Exposición"], &[
+            ("A1", "IMS", &["9/10", "NP"]),
+            ("A2", "IMS", &["4/10", "NP"]),
+            ("A3", "IMS", &["8/10", "5/10"]),
+        ]);
+        let result = diff(&old, &new);
+
+        assert!(result.renamed_evaluations.is_empty());
+        assert_eq!(result.removed_evaluations, vec!["Laboratorio"]);
+        assert_eq!(result.added_evaluations, vec!["Exposición"]);
+    }
+
+    #[test]
+    fn test_status_changes_use_attendance() {
+        let old = fixtures::table(&["Parcial", "Final"], &[("A1", "IMS", &["45/50", "NP"])]);
+        let new = fixtures::table(&["Parcial", "Final"], &[("A1", "IMS", &["45/50", "40/50"])]);
+        let sheet = AttendanceSheet::try_from(parse_csv(b"Carnet,S1,S2,S3,S4\nA1,P,A,A,A\n").unwrap()).unwrap();
+        let policy = GradingPolicy {
+            attendance: AttendancePolicy { min_percent: Some(80.0), ..AttendancePolicy::default() },
+            ..GradingPolicy::default()
+        };
+
+        // Con las notas solas pasaría a aprobado; por inasistencia sigue reprobado
+        assert_eq!(diff_with_policy(&old, &new, &policy).status_changes.len(), 1);
+        let result = diff_with_stats(
+            &GradeStats::with_attendance(&old, &policy, Some(&sheet)),
+            &GradeStats::with_attendance(&new, &policy, Some(&sheet)),
+        );
+        assert!(result.status_changes.is_empty());
+        assert_eq!(result.changed_cells.len(), 1);
+    }
+}
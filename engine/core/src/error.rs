@@ -5,6 +5,13 @@ pub enum EngineError {
     EmptyInput,
     InconsistentColumns,
     CsvParseError(String),
+    InvalidEvaluation(usize),
+    InvalidTransform(String),
+    DuplicateGradebook(String),
+    UnknownGradebook(String),
+    DuplicateCarnet(String),
+    InvalidCarnet(String),
+    InvalidState(String),
 }
 
 impl std::fmt::Display for EngineError {
@@ -15,6 +22,13 @@ impl std::fmt::Display for EngineError {
             EngineError::InconsistentColumns => write!(f, "Inconsistent number of columns in CSV data"),
             EngineError::CsvParseError(msg) => write!(f, "CSV parse error: {}", msg),
             EngineError::ExcelError => write!(f, "Error processing Excel file"),
+            EngineError::InvalidEvaluation(idx) => write!(f, "Evaluation index {} does not exist", idx),
+            EngineError::InvalidTransform(msg) => write!(f, "Invalid transform: {}", msg),
+            EngineError::DuplicateGradebook(name) => write!(f, "A gradebook named '{}' is already loaded", name),
+            EngineError::UnknownGradebook(name) => write!(f, "No gradebook named '{}'", name),
+            EngineError::DuplicateCarnet(carnet) => write!(f, "Carnet {} appears in more than one section", carnet),
+            EngineError::InvalidCarnet(carnet) => write!(f, "Carnet '{}' does not match any known format", carnet),
+            EngineError::InvalidState(msg) => write!(f, "Invalid saved state: {}", msg),
         }
     }
 }
\ No newline at end of file
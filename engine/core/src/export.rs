@@ -0,0 +1,126 @@
+//! Exportación de la tabla con su resumen a formatos para compartir.
+
+use crate::session::EngineState;
+
+/// Encabezado y una fila por estudiante: datos, notas tal como en la hoja y las columnas
+/// calculadas con la asistencia y la reposición. Base de todos los formatos de exportación.
+pub fn rows(state: &EngineState) -> (Vec<String>, Vec<Vec<String>>) {
+    let table = &state.table;
+    let students = state.stats().student_summaries();
+
+    let mut header: Vec<String> = ["Carnet", "Nombre", "Correo", "Grupo"].map(String::from).to_vec();
+    header.extend(table.evaluations.iter().cloned());
+    header.extend(["Acumulado", "Estado", "Nota"].map(String::from));
+
+    let rows = table.records.iter().zip(&students).map(|(record, student)| {
+        let mut cells = vec![record.carnet.clone(), record.name.clone(), record.email.clone(), record.group.clone()];
+        cells.extend(record.grades.iter().map(|g| g.to_string()));
+        cells.push(student.accumulated_score.map(|s| format!("{:.2}", s)).unwrap_or_default());
+        cells.push(format!("{:?}", student.status));
+        cells.push(student.converted_grade.as_ref().map(|g| g.label.clone()).unwrap_or_default());
+        cells
+    }).collect();
+
+    (header, rows)
+}
+
+pub fn to_csv(state: &EngineState) -> String {
+    let (header, rows) = rows(state);
+    let mut lines = vec![csv_line(header.iter().map(String::as_str))];
+    lines.extend(rows.iter().map(|cells| csv_line(cells.iter().map(String::as_str))));
+    lines.join("\n") + "\n"
+}
+
+/// Página HTML autocontenida con la tabla, para imprimir o enviar por correo.
+pub fn to_html(title: &str, state: &EngineState) -> String {
+    let (header, rows) = rows(state);
+    let mut html = String::from("<!DOCTYPE html>\n<html lang=\"es\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html += &format!("<title>{}</title>\n", escape_html(title));
+    html += "<style>body{font-family:sans-serif}table{border-collapse:collapse}th,td{border:1px solid #ccc;padding:4px 8px}th{background:#f1f5f9}</style>\n";
+    html += &format!("</head>\n<body>\n<h1>{}</h1>\n<table>\n<thead><tr>", escape_html(title));
+    for cell in &header {
+        html += &format!("<th>{}</th>", escape_html(cell));
+    }
+    html += "</tr></thead>\n<tbody>\n";
+    for cells in &rows {
+        html += "<tr>";
+        for cell in cells {
+            html += &format!("<td>{}</td>", escape_html(cell));
+        }
+        html += "</tr>\n";
+    }
+    html += "</tbody>\n</table>\n</body>\n</html>\n";
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn csv_line<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    cells.map(|cell| {
+        if cell.contains([',', '"', '\n']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.to_string()
+        }
+    }).collect::<Vec<_>>().join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::attendance::{AttendancePolicy, AttendanceSheet};
+    use crate::models::gradebook::{fixtures, AcademicTable};
+    use crate::parser::parse_csv;
+    use crate::rules::GradingPolicy;
+
+    fn state(table: AcademicTable) -> EngineState {
+        EngineState {
+            description_headers: Vec::new(),
+            table,
+            policy: GradingPolicy::default(),
+            attendance: None,
+            makeup_grades: Vec::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_csv_round_trips_cells() {
+        let table = fixtures::table(&["Prueba 1", "Examen, final"], &[
+            ("A1", "IMS", &["45/50", "RM"]),
+            ("A2", "ICE", &["30/50", "NP"]),
+        ]);
+        let csv = to_csv(&state(table));
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "Carnet,Nombre,Correo,Grupo,Prueba 1,\"Examen, final\",Acumulado,Estado,Nota");
+        assert_eq!(lines[1], "A1,Estudiante A1,A1@est.ulsa,IMS,45/50,RM,45.00,OnTrack,");
+
+        let raw = parse_csv(csv.as_bytes()).unwrap();
+        assert_eq!(raw.rows.len(), 2);
+        assert_eq!(raw.rows[1][5].as_deref(), Some("NP"));
+    }
+
+    #[test]
+    fn test_html_escapes_cells() {
+        let table = fixtures::table(&["Quiz <1>"], &[("A1", "IMS", &["9/10"])]);
+        let html = to_html("Cálculo & Álgebra", &state(table));
+
+        assert!(html.contains("<title>Cálculo &amp; Álgebra</title>"));
+        assert!(html.contains("<th>Quiz &lt;1&gt;</th>"));
+        assert!(html.contains("<td>9/10</td>"));
+    }
+
+    #[test]
+    fn test_status_includes_attendance() {
+        let mut state = state(fixtures::table(&["Parcial", "Final"], &[("A1", "IMS", &["45/50", "40/50"])]));
+        state.policy.attendance = AttendancePolicy { min_percent: Some(80.0), ..AttendancePolicy::default() };
+        state.attendance = Some(AttendanceSheet::try_from(parse_csv(b"Carnet,S1,S2\nA1,P,A\n").unwrap()).unwrap());
+
+        let (_, rows) = rows(&state);
+        assert_eq!(rows[0][6], "85.00");
+        assert_eq!(rows[0][7], "Failed");
+    }
+}
@@ -0,0 +1,275 @@
+//! Historial de un curso a lo largo de los ciclos, para ver tendencias entre cohortes.
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{mean, variance};
+use crate::models::domain::GradebookSummary;
+use crate::rules::GradingPolicy;
+
+/// How far apart (in share of the course evaluated) two snapshots can be and still count as
+/// "the same point in the term".
+const PROGRESS_TOLERANCE: f32 = 0.1;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct TermSnapshot {
+    pub course: String,
+    /// Term label; labels must sort chronologically ("2024-1", "2024-2", "2025-1").
+    pub term: String,
+    /// Free-form date the snapshot was taken.
+    #[serde(default)]
+    pub recorded_at: Option<String>,
+    /// Share of the course points already evaluated (0–1).
+    pub progress: f32,
+    pub total_course_points: f32,
+    pub summary: GradebookSummary,
+}
+
+impl TermSnapshot {
+    pub fn new(course: impl Into<String>, term: impl Into<String>, summary: GradebookSummary, policy: &GradingPolicy) -> Self {
+        let evaluated = summary.class.acumulated_points.unwrap_or(0.0);
+        let progress = if policy.total_course_points > 0.0 {
+            (evaluated / policy.total_course_points).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        Self {
+            course: course.into(),
+            term: term.into(),
+            recorded_at: None,
+            progress,
+            total_course_points: policy.total_course_points,
+            summary,
+        }
+    }
+
+    /// Aprobados sobre estudiantes del curso.
+    pub fn pass_rate(&self) -> Option<f32> {
+        let class = &self.summary.class;
+        (class.student_count > 0).then(|| class.approved_count as f32 / class.student_count as f32)
+    }
+
+    /// Promedio de la clase como porcentaje del total del curso.
+    pub fn average_percent(&self) -> Option<f32> {
+        let average = self.summary.class.overall_average?;
+        (self.total_course_points > 0.0).then(|| average / self.total_course_points * 100.0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct TermValue {
+    pub term: String,
+    pub value: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
+pub struct Trend {
+    pub terms: Vec<TermValue>,
+    /// Least squares change per term; None with fewer than two terms.
+    pub slope_per_term: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct EvaluationDrift {
+    pub evaluation: String,
+    /// Mean proportion of the evaluation's points earned each term (lower is harder).
+    pub trend: Trend,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
+pub struct CohortComparison {
+    pub progress: f32,
+    pub current_average: f32,
+    pub historical_mean: f32,
+    pub historical_std_dev: Option<f32>,
+    pub z_score: Option<f32>,
+    /// Share of past terms whose average at this point was below the current one.
+    pub percentile: f32,
+    pub terms_compared: Vec<TermValue>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HistoryStore {
+    snapshots: Vec<TermSnapshot>,
+}
+
+impl HistoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Agrega un snapshot; uno del mismo curso, ciclo y avance lo reemplaza.
+    pub fn record(&mut self, snapshot: TermSnapshot) {
+        self.snapshots.retain(|s| {
+            !(s.course == snapshot.course && s.term == snapshot.term && (s.progress - snapshot.progress).abs() < f32::EPSILON)
+        });
+        self.snapshots.push(snapshot);
+    }
+
+    pub fn snapshots(&self) -> &[TermSnapshot] {
+        &self.snapshots
+    }
+
+    /// Último snapshot (el de mayor avance) de cada ciclo del curso, en orden de ciclo.
+    pub fn final_snapshots(&self, course: &str) -> Vec<&TermSnapshot> {
+        let mut finals: Vec<&TermSnapshot> = Vec::new();
+        for snapshot in self.snapshots.iter().filter(|s| s.course == course) {
+            match finals.iter_mut().find(|f| f.term == snapshot.term) {
+                Some(existing) if snapshot.progress > existing.progress => *existing = snapshot,
+                Some(_) => {}
+                None => finals.push(snapshot),
+            }
+        }
+        finals.sort_by(|a, b| a.term.cmp(&b.term));
+        finals
+    }
+
+    pub fn pass_rate_trend(&self, course: &str) -> Trend {
+        trend(self.final_snapshots(course).iter()
+            .filter_map(|s| s.pass_rate().map(|value| TermValue { term: s.term.clone(), value })))
+    }
+
+    /// Promedio final (en % del curso) por ciclo: una pendiente positiva sugiere inflación.
+    pub fn inflation_trend(&self, course: &str) -> Trend {
+        trend(self.final_snapshots(course).iter()
+            .filter_map(|s| s.average_percent().map(|value| TermValue { term: s.term.clone(), value })))
+    }
+
+    /// Cambio de dificultad de cada evaluación, emparejada por nombre entre ciclos.
+    pub fn difficulty_drift(&self, course: &str) -> Vec<EvaluationDrift> {
+        let finals = self.final_snapshots(course);
+        let mut names: Vec<&str> = Vec::new();
+        for snapshot in &finals {
+            for evaluation in &snapshot.summary.evaluations {
+                if !names.contains(&evaluation.name.as_str()) {
+                    names.push(&evaluation.name);
+                }
+            }
+        }
+
+        names.into_iter().map(|name| {
+            let values = finals.iter().filter_map(|s| {
+                let evaluation = s.summary.evaluations.iter().find(|e| e.name == name)?;
+                let max = evaluation.max_possible_score.filter(|m| *m > 0.0)?;
+                Some(TermValue { term: s.term.clone(), value: evaluation.average? / max })
+            });
+            EvaluationDrift { evaluation: name.to_string(), trend: trend(values) }
+        }).collect()
+    }
+
+    /// Compara el ciclo en curso con los anteriores en el mismo punto del ciclo.
+    pub fn compare_current(&self, current: &TermSnapshot) -> Option<CohortComparison> {
+        let current_average = current.summary.class.overall_average?;
+
+        let mut terms_compared: Vec<TermValue> = Vec::new();
+        for snapshot in self.snapshots.iter().filter(|s| s.course == current.course && s.term != current.term) {
+            let distance = (snapshot.progress - current.progress).abs();
+            if distance > PROGRESS_TOLERANCE {
+                continue;
+            }
+            let Some(average) = snapshot.summary.class.overall_average else { continue };
+
+            // Un valor por ciclo: el snapshot más cercano en avance
+            let closest = self.snapshots.iter()
+                .filter(|s| s.course == current.course && s.term == snapshot.term)
+                .all(|s| (s.progress - current.progress).abs() >= distance);
+            if closest && !terms_compared.iter().any(|t| t.term == snapshot.term) {
+                terms_compared.push(TermValue { term: snapshot.term.clone(), value: average });
+            }
+        }
+        terms_compared.sort_by(|a, b| a.term.cmp(&b.term));
+
+        let values: Vec<f32> = terms_compared.iter().map(|t| t.value).collect();
+        let historical_mean = mean(&values)?;
+        let historical_std_dev = variance(&values).map(f32::sqrt);
+        let z_score = historical_std_dev
+            .filter(|sd| *sd > f32::EPSILON)
+            .map(|sd| (current_average - historical_mean) / sd);
+        let below = values.iter().filter(|v| **v < current_average).count();
+
+        Some(CohortComparison {
+            progress: current.progress,
+            current_average,
+            historical_mean,
+            historical_std_dev,
+            z_score,
+            percentile: below as f32 / values.len() as f32,
+            terms_compared,
+        })
+    }
+}
+
+fn trend(values: impl Iterator<Item = TermValue>) -> Trend {
+    let terms: Vec<TermValue> = values.collect();
+    let ys: Vec<f32> = terms.iter().map(|t| t.value).collect();
+    let xs: Vec<f32> = (0..ys.len()).map(|i| i as f32).collect();
+
+    let slope_per_term = (ys.len() >= 2).then(|| {
+        let (mx, my) = (mean(&xs).unwrap_or(0.0), mean(&ys).unwrap_or(0.0));
+        let sxx: f32 = xs.iter().map(|x| (x - mx).powi(2)).sum();
+        let sxy: f32 = xs.iter().zip(&ys).map(|(x, y)| (x - mx) * (y - my)).sum();
+        sxy / sxx
+    });
+
+    Trend { terms, slope_per_term }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+    use crate::models::stats::GradeStats;
+
+    fn snapshot(term: &str, rows: &[(&str, &str, &[&str])]) -> TermSnapshot {
+        let table = fixtures::table(&["Parcial", "Final"], rows);
+        let policy = GradingPolicy::default();
+        TermSnapshot::new("Cálculo", term, GradeStats::with_policy(&table, &policy).summary(), &policy)
+    }
+
+    fn store() -> HistoryStore {
+        let mut store = HistoryStore::new();
+        store.record(snapshot("2023-2", &[("A1", "IMS", &["30/50", "30/50"]), ("A2", "IMS", &["20/50", "25/50"])]));
+        store.record(snapshot("2024-1", &[("B1", "IMS", &["40/50", "35/50"]), ("B2", "IMS", &["25/50", "30/50"])]));
+        store.record(snapshot("2024-2", &[("C1", "IMS", &["45/50", "40/50"]), ("C2", "IMS", &["40/50", "35/50"])]));
+        // Mitad de ciclo de 2024-2: sólo el parcial
+        store.record(snapshot("2024-2", &[("C1", "IMS", &["45/50", "NP"]), ("C2", "IMS", &["40/50", "NP"])]));
+        store
+    }
+
+    #[test]
+    fn test_trends() {
+        let store = store();
+        assert_eq!(store.final_snapshots("Cálculo").len(), 3);
+
+        let pass = store.pass_rate_trend("Cálculo");
+        let rates: Vec<f32> = pass.terms.iter().map(|t| t.value).collect();
+        assert_eq!(rates, vec![0.0, 0.5, 1.0]);
+        assert!((pass.slope_per_term.unwrap() - 0.5).abs() < 1e-5);
+
+        let inflation = store.inflation_trend("Cálculo");
+        assert!(inflation.slope_per_term.unwrap() > 0.0);
+
+        let drift = store.difficulty_drift("Cálculo");
+        assert_eq!(drift.len(), 2);
+        assert_eq!(drift[0].evaluation, "Parcial");
+        assert!((drift[0].trend.terms[0].value - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_compare_current_term() {
+        let store = store();
+        // 2025-1 a mitad de ciclo, por debajo de 2024-2 en el mismo punto
+        let current = snapshot("2025-1", &[("D1", "IMS", &["20/50", "NP"]), ("D2", "IMS", &["22/50", "NP"])]);
+        let comparison = store.compare_current(&current).unwrap();
+
+        assert_eq!(comparison.progress, 0.5);
+        assert_eq!(comparison.terms_compared.len(), 1);
+        assert_eq!(comparison.terms_compared[0].term, "2024-2");
+        assert_eq!(comparison.historical_mean, 42.5);
+        assert_eq!(comparison.percentile, 0.0);
+    }
+}
@@ -1,12 +1,41 @@
 pub mod api {
-    pub use crate::parser::parse_excel;
+    pub use crate::parser::{parse_csv, parse_excel, parse_sheet};
     pub use crate::models::raw::RawTable;
     pub use crate::models::gradebook::AcademicTable;
+    pub use crate::models::carnet::{Carnet, CarnetFormat};
+    pub use crate::models::attendance::{AttendanceMark, AttendancePolicy, AttendanceSheet, AttendanceSummary};
     pub use crate::models::stats::{GradeStats, GradeStatsOwned};
-    pub use crate::rules::AcademicStatus;
+    pub use crate::rules::{AcademicStatus, CategoryRule, GradingPolicy};
+    pub use crate::analysis::items::{item_analysis, ItemAnalysis, ItemFlag};
+    pub use crate::analysis::reliability::{correlation_matrix, reliability, CorrelationMatrix, ReliabilityReport};
+    pub use crate::analysis::groups::{group_report, GroupReport, GroupSummary};
+    pub use crate::analysis::projection::{project_students, StudentProjection};
+    pub use crate::analysis::anomalies::{detect_anomalies, Anomaly, AnomalyKind, Severity};
+    pub use crate::analysis::similarity::{similarity_report, SimilarityOptions, SimilarityReport};
+    pub use crate::analysis::cohorts::{cohort_report, CohortOptions, CohortReport};
+    pub use crate::transform::{Curve, CellChange, CurvePreview};
+    pub use crate::scale::{ConvertedGrade, GradeBand, GradeBucket, GradeScale};
+    pub use crate::workspace::{Gradebook, Workspace, StudentCourseLoad, SubjectSummary};
+    pub use crate::merge::{merge_sections, DuplicatePolicy, MergeOptions, MergeReport};
+    pub use crate::diff::{diff, diff_with_policy, diff_with_stats, GradebookDiff};
+    pub use crate::history::{HistoryStore, TermSnapshot, Trend, EvaluationDrift, CohortComparison};
+    pub use crate::session::{Annotation, EngineState, STATE_VERSION};
+    pub use crate::makeup::{makeup_summary, MakeupGrade, MakeupPolicy, MakeupReplacement, MakeupSummary};
+    pub use crate::batch::{BatchError, BatchItem, BatchReport, GradebookInfo};
 }
 
 pub mod error;
 pub mod parser;
 pub mod models;
-pub mod rules;
\ No newline at end of file
+pub mod rules;
+pub mod analysis;
+pub mod transform;
+pub mod scale;
+pub mod makeup;
+pub mod workspace;
+pub mod merge;
+pub mod diff;
+pub mod history;
+pub mod session;
+pub mod export;
+pub mod batch;
\ No newline at end of file
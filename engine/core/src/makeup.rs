@@ -0,0 +1,330 @@
+//! Examen de reposición (convocatoria): quién tiene derecho y cómo cambia la nota final.
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::gradebook::{AcademicTable, GradeValue, StudentRecord};
+use crate::models::stats::{normalized_score, compute_evaluation_max_scores, GradeStats};
+use crate::rules::{AcademicStatus, GradingPolicy};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct MakeupPolicy {
+    /// Points the make-up exam is graded over.
+    pub total_points: f32,
+    /// Minimum accumulated score to sit the make-up.
+    pub min_score: f32,
+    /// Minimum attendance percentage, checked only when attendance is known.
+    #[serde(default)]
+    pub min_attendance_percent: Option<f32>,
+    pub replaces: MakeupReplacement,
+    /// Highest final score reachable through the make-up (often the passing score).
+    #[serde(default)]
+    pub max_final_score: Option<f32>,
+}
+
+impl Default for MakeupPolicy {
+    fn default() -> Self {
+        Self {
+            total_points: 100.0,
+            min_score: 0.0,
+            min_attendance_percent: None,
+            replaces: MakeupReplacement::WholeCourse,
+            max_final_score: None,
+        }
+    }
+}
+
+/// Qué notas sustituye la reposición. Nunca baja una nota: sólo reemplaza si es mayor.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+#[serde(tag = "type")]
+pub enum MakeupReplacement {
+    /// The make-up percentage becomes the whole course score.
+    WholeCourse,
+    /// Every listed evaluation (by name or category) takes the make-up percentage.
+    Evaluations { evaluations: Vec<String> },
+    /// Only the lowest of the listed evaluations takes the make-up percentage.
+    LowestOf { evaluations: Vec<String> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct MakeupGrade {
+    pub carnet: String,
+    pub score: f32, // Sobre MakeupPolicy::total_points
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub enum IneligibleReason {
+    AlreadyPassed,
+    /// Not failed yet: there are still points in play during the term.
+    StillInProgress,
+    Withdrawn,
+    BelowMinimumScore,
+    LowAttendance,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct MakeupCandidate {
+    pub carnet: String,
+    pub name: String,
+    pub accumulated_score: Option<f32>,
+    pub status: AcademicStatus,
+    pub attendance_percent: Option<f32>,
+    pub ineligible_reason: Option<IneligibleReason>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct MakeupOutcome {
+    pub carnet: String,
+    pub makeup_score: f32,
+    pub previous_score: Option<f32>,
+    pub final_score: f32,
+    pub previous_status: AcademicStatus,
+    pub final_status: AcademicStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
+pub struct MakeupSummary {
+    pub eligible: Vec<MakeupCandidate>,
+    pub ineligible: Vec<MakeupCandidate>,
+    /// One entry per make-up grade entered for an eligible student.
+    pub outcomes: Vec<MakeupOutcome>,
+}
+
+/// Elegibles, no elegibles y resultado de cada reposición ingresada. Sólo quienes ya reprobaron
+/// (`Failed`) pueden presentarla; el estado y la asistencia salen de `stats`.
+pub fn makeup_summary(stats: &GradeStats, grades: &[MakeupGrade]) -> MakeupSummary {
+    let table = stats.table();
+    let policy = stats.policy();
+    let students = stats.student_summaries();
+    let makeup = &policy.makeup;
+
+    let mut eligible = Vec::new();
+    let mut ineligible = Vec::new();
+    let mut outcomes = Vec::new();
+
+    for (i, (record, student)) in table.records.iter().zip(students).enumerate() {
+        let score = student.accumulated_score.unwrap_or(0.0);
+
+        let ineligible_reason = if record.final_grade == GradeValue::Withdrawn {
+            Some(IneligibleReason::Withdrawn)
+        } else if student.status == AcademicStatus::Approved {
+            Some(IneligibleReason::AlreadyPassed)
+        } else if student.status != AcademicStatus::Failed {
+            Some(IneligibleReason::StillInProgress)
+        } else if score >= policy.passing_score {
+            // Reprobado con nota suficiente: sólo la asistencia explica el estado
+            Some(IneligibleReason::LowAttendance)
+        } else if score < makeup.min_score {
+            Some(IneligibleReason::BelowMinimumScore)
+        } else if makeup.min_attendance_percent.zip(student.attendance_percent).is_some_and(|(min, a)| a < min) {
+            Some(IneligibleReason::LowAttendance)
+        } else {
+            None
+        };
+
+        let candidate = MakeupCandidate {
+            carnet: record.carnet.clone(),
+            name: record.name.clone(),
+            accumulated_score: student.accumulated_score,
+            status: student.status.clone(),
+            attendance_percent: student.attendance_percent,
+            ineligible_reason,
+        };
+
+        if candidate.ineligible_reason.is_some() {
+            ineligible.push(candidate);
+            continue;
+        }
+
+        if let Some(grade) = grades.iter().find(|g| g.carnet == record.carnet) {
+            let final_score = score_with_makeup(table, policy, i, score, grade.score);
+            outcomes.push(MakeupOutcome {
+                carnet: record.carnet.clone(),
+                makeup_score: grade.score,
+                previous_score: student.accumulated_score,
+                final_score,
+                previous_status: student.status.clone(),
+                final_status: stats.closed_status(i, final_score),
+            });
+        }
+        eligible.push(candidate);
+    }
+
+    MakeupSummary { eligible, ineligible, outcomes }
+}
+
+fn score_with_makeup(table: &AcademicTable, policy: &GradingPolicy, student_idx: usize, previous: f32, makeup_score: f32) -> f32 {
+    let makeup = &policy.makeup;
+    let percent = if makeup.total_points > 0.0 { (makeup_score / makeup.total_points).clamp(0.0, 1.0) } else { 0.0 };
+
+    let replaced = match &makeup.replaces {
+        MakeupReplacement::WholeCourse => percent * policy.total_course_points,
+        MakeupReplacement::Evaluations { evaluations } | MakeupReplacement::LowestOf { evaluations } => {
+            let only_lowest = matches!(makeup.replaces, MakeupReplacement::LowestOf { .. });
+            // Sólo se recalcula la fila del estudiante
+            let mut record = table.records[student_idx].clone();
+            replace_grades(table, &mut record, evaluations, percent, only_lowest);
+            policy.totals_for(table, &record).accumulated.unwrap_or(0.0)
+        }
+    };
+
+    let capped = match makeup.max_final_score {
+        Some(cap) => replaced.min(cap),
+        None => replaced,
+    };
+    capped.max(previous)
+}
+
+/// Sube al porcentaje de la reposición las evaluaciones indicadas (o sólo la más baja).
+fn replace_grades(table: &AcademicTable, record: &mut StudentRecord, targets: &[String], percent: f32, only_lowest: bool) {
+    let max_scores = compute_evaluation_max_scores(table);
+    let categories = table.evaluation_categories();
+    let matches = |e: usize| {
+        targets.iter().any(|t| {
+            t.trim().eq_ignore_ascii_case(table.evaluations[e].trim()) || t.trim().eq_ignore_ascii_case(&categories[e])
+        })
+    };
+
+    let mut selected: Vec<usize> = (0..table.evaluations.len()).filter(|&e| matches(e)).collect();
+    if only_lowest {
+        // Una evaluación sin nota cuenta como 0%
+        let lowest = selected.iter().copied().min_by(|&a, &b| {
+            let pa = normalized_score(&record.grades[a], max_scores[a]).unwrap_or(0.0);
+            let pb = normalized_score(&record.grades[b], max_scores[b]).unwrap_or(0.0);
+            pa.total_cmp(&pb)
+        });
+        selected = lowest.into_iter().collect();
+    }
+
+    for e in selected {
+        let Some(max) = (match &record.grades[e] {
+            GradeValue::Fraction { total, .. } => Some(*total),
+            _ => max_scores[e],
+        }) else {
+            continue;
+        };
+        let current = normalized_score(&record.grades[e], Some(max)).unwrap_or(0.0);
+        if percent > current {
+            record.grades[e] = GradeValue::Fraction { obtained: percent * max, total: max };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::attendance::AttendanceSheet;
+    use crate::models::gradebook::fixtures;
+    use crate::parser::parse_csv;
+
+    fn sample_table() -> AcademicTable {
+        fixtures::table(&["Parcial 1", "Parcial 2", "Examen Final"], &[
+            ("A1", "IMS", &["30/30", "28/30", "35/40"]), // 93: aprobado
+            ("A2", "IMS", &["20/30", "10/30", "25/40"]), // 55: reposición
+            ("A3", "IMS", &["5/30", "8/30", "10/40"]),   // 23: bajo el mínimo
+            ("A4", "IMS", &["15/30", "12/30", "30/40"]), // 57: poca asistencia
+            ("A5", "IMS", &["20/30", "NP", "NP"]),       // 20 con 70 en juego: todavía no reprueba
+        ])
+    }
+
+    fn attendance() -> AttendanceSheet {
+        let csv = "Carnet,S1,S2,S3,S4,S5\nA1,P,P,P,P,P\nA2,P,P,P,P,A\nA3,,,,,\nA4,P,P,P,A,A\nA5,P,P,P,P,P\n";
+        AttendanceSheet::try_from(parse_csv(csv.as_bytes()).unwrap()).unwrap()
+    }
+
+    fn policy(replaces: MakeupReplacement) -> GradingPolicy {
+        GradingPolicy {
+            makeup: MakeupPolicy {
+                min_score: 40.0,
+                min_attendance_percent: Some(80.0),
+                replaces,
+                max_final_score: Some(80.0),
+                ..MakeupPolicy::default()
+            },
+            ..GradingPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_eligibility() {
+        let table = sample_table();
+        let policy = policy(MakeupReplacement::WholeCourse);
+        let sheet = attendance();
+        let summary = makeup_summary(&GradeStats::with_attendance(&table, &policy, Some(&sheet)), &[]);
+
+        let carnets: Vec<&str> = summary.eligible.iter().map(|c| c.carnet.as_str()).collect();
+        assert_eq!(carnets, vec!["A2"]);
+
+        let reasons: Vec<Option<IneligibleReason>> = summary.ineligible.iter().map(|c| c.ineligible_reason.clone()).collect();
+        assert_eq!(reasons, vec![
+            Some(IneligibleReason::AlreadyPassed),
+            Some(IneligibleReason::BelowMinimumScore),
+            Some(IneligibleReason::LowAttendance),
+            Some(IneligibleReason::StillInProgress),
+        ]);
+    }
+
+    #[test]
+    fn test_failed_by_attendance_with_a_passing_score() {
+        let table = sample_table();
+        let mut policy = policy(MakeupReplacement::WholeCourse);
+        policy.attendance.min_percent = Some(70.0);
+        // A1 tiene 93 pero asistió al 40%
+        let csv = "Carnet,S1,S2,S3,S4,S5\nA1,P,P,A,A,A\nA2,P,P,P,P,P\nA3,P,P,P,P,P\nA4,P,P,P,P,P\nA5,P,P,P,P,P\n";
+        let sheet = AttendanceSheet::try_from(parse_csv(csv.as_bytes()).unwrap()).unwrap();
+        let summary = makeup_summary(&GradeStats::with_attendance(&table, &policy, Some(&sheet)), &[]);
+
+        let a1 = summary.ineligible.iter().find(|c| c.carnet == "A1").unwrap();
+        assert_eq!(a1.status, AcademicStatus::Failed);
+        assert_eq!(a1.ineligible_reason, Some(IneligibleReason::LowAttendance));
+    }
+
+    #[test]
+    fn test_outcomes() {
+        let table = sample_table();
+        let grades = vec![MakeupGrade { carnet: "A2".to_string(), score: 90.0 }];
+        let summary_with = |policy: &GradingPolicy, grades: &[MakeupGrade]| {
+            makeup_summary(&GradeStats::with_policy(&table, policy), grades)
+        };
+
+        let whole = summary_with(&policy(MakeupReplacement::WholeCourse), &grades);
+        // 90% del curso, topado en 80
+        assert_eq!(whole.outcomes[0].final_score, 80.0);
+        assert_eq!(whole.outcomes[0].final_status, AcademicStatus::Approved);
+
+        let lowest = MakeupReplacement::LowestOf { evaluations: vec!["Parcial".to_string()] };
+        let summary = summary_with(&policy(lowest), &grades);
+        // Parcial 2 (10/30) pasa a 27/30: 55 + 17 = 72
+        assert!((summary.outcomes[0].final_score - 72.0).abs() < 1e-4);
+        assert_eq!(summary.outcomes[0].previous_status, AcademicStatus::Failed);
+
+        let low_grade = vec![MakeupGrade { carnet: "A2".to_string(), score: 20.0 }];
+        let summary = summary_with(&policy(MakeupReplacement::WholeCourse), &low_grade);
+        // La reposición nunca baja la nota
+        assert_eq!(summary.outcomes[0].final_score, 55.0);
+        assert_eq!(summary.outcomes[0].final_status, AcademicStatus::Failed);
+    }
+
+    #[test]
+    fn test_makeup_changes_the_student_summary() {
+        let table = sample_table();
+        let policy = policy(MakeupReplacement::WholeCourse);
+        // A5 todavía no puede presentar: su nota se ignora
+        let grades = vec![
+            MakeupGrade { carnet: "A2".to_string(), score: 90.0 },
+            MakeupGrade { carnet: "A5".to_string(), score: 90.0 },
+        ];
+
+        let students = GradeStats::with_policy(&table, &policy).with_makeup(&grades).student_summaries();
+        assert_eq!(students[1].accumulated_score, Some(80.0));
+        assert_eq!(students[1].status, AcademicStatus::Approved);
+        assert_eq!(students[4].accumulated_score, Some(20.0));
+        assert_ne!(students[4].status, AcademicStatus::Approved);
+    }
+}
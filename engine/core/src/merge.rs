@@ -0,0 +1,258 @@
+//! Une los archivos de varias secciones del mismo curso en una sola tabla.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::EngineError;
+use crate::models::gradebook::{AcademicTable, GradeValue, StudentRecord};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(from_wasm_abi))]
+pub struct MergeOptions {
+    /// Lowest name similarity (0–1) for two differently written evaluations to be aligned.
+    pub min_similarity: f32,
+    pub duplicates: DuplicatePolicy,
+}
+
+impl Default for MergeOptions {
+    fn default() -> Self {
+        Self { min_similarity: 0.8, duplicates: DuplicatePolicy::Report }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub enum DuplicatePolicy {
+    /// Fail the merge on the first repeated carnet.
+    Reject,
+    /// Keep the first record and list the repeats.
+    Report,
+}
+
+/// Evaluación alineada por parecido de nombre; el docente debe revisarla.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct EvaluationMatch {
+    pub section: String,
+    pub evaluation: String,
+    pub matched_to: String,
+    pub similarity: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct AddedEvaluation {
+    pub section: String,
+    pub evaluation: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct DuplicateStudent {
+    pub carnet: String,
+    /// Sections where the carnet appears; the record of the first one is kept.
+    pub sections: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
+pub struct MergeReport {
+    pub table: AcademicTable,
+    pub fuzzy_matches: Vec<EvaluationMatch>,
+    /// Evaluations with no counterpart in earlier sections, appended as new columns.
+    pub added_evaluations: Vec<AddedEvaluation>,
+    pub duplicates: Vec<DuplicateStudent>,
+}
+
+/// `sections` son pares (nombre de sección, tabla). Las columnas siguen el orden de la primera
+/// sección; cada registro queda marcado con su sección de origen.
+pub fn merge_sections(sections: &[(String, AcademicTable)], options: &MergeOptions) -> Result<MergeReport, EngineError> {
+    if sections.is_empty() {
+        return Err(EngineError::EmptyInput);
+    }
+
+    let mut evaluations: Vec<String> = Vec::new();
+    let mut fuzzy_matches = Vec::new();
+    let mut added_evaluations = Vec::new();
+
+    // 1. Columna de destino de cada evaluación de cada sección
+    let column_maps: Vec<Vec<usize>> = sections.iter().enumerate().map(|(s, (section, table))| {
+        let mut used = vec![false; evaluations.len()];
+        table.evaluations.iter().map(|name| {
+            let key = normalize(name);
+            let exact = evaluations.iter().enumerate()
+                .position(|(i, e)| !used[i] && normalize(e) == key);
+
+            let target = exact.or_else(|| {
+                let (best, similarity) = evaluations.iter().enumerate()
+                    .filter(|(i, e)| !used[*i] && same_numbers(e, name))
+                    .map(|(i, e)| (i, similarity(&normalize(e), &key)))
+                    .max_by(|a, b| a.1.total_cmp(&b.1))?;
+                (similarity >= options.min_similarity).then(|| {
+                    fuzzy_matches.push(EvaluationMatch {
+                        section: section.clone(),
+                        evaluation: name.clone(),
+                        matched_to: evaluations[best].clone(),
+                        similarity,
+                    });
+                    best
+                })
+            });
+
+            let target = target.unwrap_or_else(|| {
+                evaluations.push(name.clone());
+                used.push(false);
+                if s > 0 {
+                    added_evaluations.push(AddedEvaluation { section: section.clone(), evaluation: name.clone() });
+                }
+                evaluations.len() - 1
+            });
+            used[target] = true;
+            target
+        }).collect()
+    }).collect();
+
+    // 2. Registros, sin repetir carnets
+    let mut records: Vec<StudentRecord> = Vec::new();
+    let mut duplicates: Vec<DuplicateStudent> = Vec::new();
+
+    for ((section, table), column_map) in sections.iter().zip(&column_maps) {
+        for record in &table.records {
+            let carnet = record.carnet.trim();
+            if let Some(first) = records.iter().find(|r| r.carnet.trim() == carnet) {
+                if options.duplicates == DuplicatePolicy::Reject {
+                    return Err(EngineError::DuplicateCarnet(carnet.to_string()));
+                }
+                match duplicates.iter_mut().find(|d| d.carnet == carnet) {
+                    Some(duplicate) => duplicate.sections.push(section.clone()),
+                    None => duplicates.push(DuplicateStudent {
+                        carnet: carnet.to_string(),
+                        sections: vec![first.section.clone().unwrap_or_default(), section.clone()],
+                    }),
+                }
+                continue;
+            }
+
+            // Evaluaciones que la sección no tiene quedan como ausentes
+            let mut grades = vec![GradeValue::Absent; evaluations.len()];
+            for (grade, &target) in record.grades.iter().zip(column_map) {
+                grades[target] = grade.clone();
+            }
+            records.push(StudentRecord {
+                grades,
+                section: Some(section.clone()),
+                ..record.clone()
+            });
+        }
+    }
+
+    Ok(MergeReport {
+        table: AcademicTable { evaluations, records },
+        fuzzy_matches,
+        added_evaluations,
+        duplicates,
+    })
+}
+
+/// Minúsculas, sin tildes y con espacios simples.
+pub(crate) fn normalize(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'ä' => 'a',
+            'é' | 'è' | 'ë' => 'e',
+            'í' | 'ì' | 'ï' => 'i',
+            'ó' | 'ò' | 'ö' => 'o',
+            'ú' | 'ù' | 'ü' => 'u',
+            'ñ' => 'n',
+            c => c,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// "Parcial 1" y "Parcial 2" se parecen mucho pero nunca son la misma evaluación.
+pub(crate) fn same_numbers(a: &str, b: &str) -> bool {
+    let digits = |s: &str| s.chars().filter(char::is_ascii_digit).collect::<String>();
+    digits(a) == digits(b)
+}
+
+/// 1 - distancia de Levenshtein / longitud mayor.
+pub(crate) fn similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    1.0 - previous[b.len()] as f32 / longest as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+
+    fn sections() -> Vec<(String, AcademicTable)> {
+        vec![
+            ("A".to_string(), fixtures::table(&["Parcial 1", "Parcial 2", "Examen Final"], &[
+                ("A1", "IMS", &["20/30", "25/30", "30/40"]),
+                ("A2", "IMS", &["15/30", "18/30", "22/40"]),
+            ])),
+            ("B".to_string(), fixtures::table(&["parcial 1", "Parcial 2", "Exam. Final", "Proyecto"], &[
+                ("B1", "ICE", &["28/30", "27/30", "35/40", "9/10"]),
+                ("A2", "ICE", &["10/30", "10/30", "10/40", "5/10"]),
+            ])),
+            ("C".to_string(), fixtures::table(&["Examen Final", "Parcial 1"], &[
+                ("C1", "IMS", &["38/40", "29/30"]),
+            ])),
+        ]
+    }
+
+    #[test]
+    fn test_merge_aligns_evaluations() {
+        let report = merge_sections(&sections(), &MergeOptions::default()).unwrap();
+        let table = &report.table;
+
+        assert_eq!(table.evaluations, vec!["Parcial 1", "Parcial 2", "Examen Final", "Proyecto"]);
+        assert_eq!(table.records.len(), 4);
+
+        // "Exam. Final" se alinea por parecido y queda en la lista de revisión
+        assert_eq!(report.fuzzy_matches.len(), 1);
+        assert_eq!(report.fuzzy_matches[0].matched_to, "Examen Final");
+        assert_eq!(report.added_evaluations.len(), 1);
+        assert_eq!(report.added_evaluations[0].evaluation, "Proyecto");
+
+        // Sección C en otro orden y sin Parcial 2 ni Proyecto
+        let c1 = &table.records[3];
+        assert_eq!(c1.section.as_deref(), Some("C"));
+        assert_eq!(c1.grades[0], GradeValue::Fraction { obtained: 29.0, total: 30.0 });
+        assert_eq!(c1.grades[1], GradeValue::Absent);
+        assert_eq!(c1.grades[2], GradeValue::Fraction { obtained: 38.0, total: 40.0 });
+    }
+
+    #[test]
+    fn test_duplicate_carnets() {
+        let report = merge_sections(&sections(), &MergeOptions::default()).unwrap();
+        assert_eq!(report.duplicates.len(), 1);
+        assert_eq!(report.duplicates[0].sections, vec!["A", "B"]);
+        // Se conserva el registro de la primera sección
+        let a2 = report.table.records.iter().find(|r| r.carnet == "A2").unwrap();
+        assert_eq!(a2.section.as_deref(), Some("A"));
+
+        let reject = MergeOptions { duplicates: DuplicatePolicy::Reject, ..MergeOptions::default() };
+        assert!(matches!(merge_sections(&sections(), &reject), Err(EngineError::DuplicateCarnet(c)) if c == "A2"));
+    }
+}
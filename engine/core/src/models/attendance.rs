@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::EngineError;
+use crate::models::gradebook::AcademicTable;
+use crate::models::raw::RawTable;
+
+/// Encabezados de identificación que no son sesiones.
+const IDENTITY_HEADERS: [&str; 9] = ["#", "CARNET", "NOMBRE", "ALUMNO", "ESTUDIANTE", "CORREO", "EMAIL", "GRUPO", "CARRERA"];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum AttendanceMark {
+    Present,
+    Absent,
+    Late,
+    Excused,
+}
+
+impl AttendanceMark {
+    /// "P"/"Presente"/"1", "A"/"Ausente"/"F"/"0", "T"/"Tarde", "J"/"Justificada"/"E".
+    pub fn parse(cell: &str) -> Option<Self> {
+        match cell.trim().to_uppercase().as_str() {
+            "P" | "PRESENTE" | "X" | "1" => Some(AttendanceMark::Present),
+            "A" | "AUSENTE" | "F" | "FALTA" | "0" => Some(AttendanceMark::Absent),
+            "T" | "TARDE" | "TARDANZA" | "L" => Some(AttendanceMark::Late),
+            "J" | "JUSTIFICADA" | "JUSTIFICADO" | "E" => Some(AttendanceMark::Excused),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudentAttendance {
+    pub carnet: String,
+    /// One entry per session; None when the cell is empty or unreadable.
+    pub marks: Vec<Option<AttendanceMark>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttendanceSheet {
+    pub sessions: Vec<String>,
+    pub records: Vec<StudentAttendance>,
+}
+
+/// Regla de asistencia del reglamento.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct AttendancePolicy {
+    /// Below this percentage the student is Failed regardless of grades.
+    #[serde(default)]
+    pub min_percent: Option<f32>,
+    /// How much a late arrival counts as attendance (1.0 = same as present).
+    pub late_weight: f32,
+}
+
+impl Default for AttendancePolicy {
+    fn default() -> Self {
+        Self { min_percent: None, late_weight: 1.0 }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct AttendanceSummary {
+    pub carnet: String,
+    pub present: usize,
+    pub absent: usize,
+    pub late: usize,
+    pub excused: usize,
+    /// Attended sessions over recorded, non-excused sessions; None if there are none.
+    pub percent: Option<f32>,
+}
+
+impl TryFrom<RawTable> for AttendanceSheet {
+    type Error = EngineError;
+
+    /// Espera los encabezados alineados con las filas (`parse_sheet` / `parse_csv`).
+    fn try_from(raw: RawTable) -> Result<Self, Self::Error> {
+        let carnet_idx = raw.headers.iter()
+            .position(|h| h.eq_ignore_ascii_case("CARNET"))
+            .ok_or(EngineError::InconsistentColumns)?;
+
+        let session_columns: Vec<usize> = raw.headers.iter().enumerate()
+            .skip(carnet_idx + 1)
+            .filter(|(_, h)| !h.is_empty() && !IDENTITY_HEADERS.contains(&h.to_uppercase().as_str()))
+            .map(|(i, _)| i)
+            .collect();
+        if session_columns.is_empty() {
+            return Err(EngineError::InconsistentColumns);
+        }
+
+        let records = raw.rows.iter()
+            .filter_map(|row| {
+                let carnet = row.get(carnet_idx)?.clone()?;
+                let marks = session_columns.iter()
+                    .map(|&c| row.get(c).cloned().flatten().and_then(|cell| AttendanceMark::parse(&cell)))
+                    .collect();
+                Some(StudentAttendance { carnet, marks })
+            })
+            .collect();
+
+        Ok(AttendanceSheet {
+            sessions: session_columns.iter().map(|&c| raw.headers[c].clone()).collect(),
+            records,
+        })
+    }
+}
+
+impl AttendanceSheet {
+    pub fn summaries(&self, policy: &AttendancePolicy) -> Vec<AttendanceSummary> {
+        self.records.iter().map(|r| summarize(r, policy)).collect()
+    }
+
+    /// Une las hojas de varias secciones. Las sesiones se alinean por nombre; las que una sección
+    /// no tuvo quedan vacías y no cuentan en su porcentaje. Un carnet repetido conserva la primera hoja.
+    pub fn merge(sheets: &[&AttendanceSheet]) -> AttendanceSheet {
+        let mut sessions: Vec<String> = Vec::new();
+        for sheet in sheets {
+            for session in &sheet.sessions {
+                if !sessions.iter().any(|s| s.trim().eq_ignore_ascii_case(session.trim())) {
+                    sessions.push(session.clone());
+                }
+            }
+        }
+
+        let mut records: Vec<StudentAttendance> = Vec::new();
+        for sheet in sheets {
+            let columns: Vec<usize> = sheet.sessions.iter()
+                .map(|session| sessions.iter().position(|s| s.trim().eq_ignore_ascii_case(session.trim())).expect("session was collected"))
+                .collect();
+            for record in &sheet.records {
+                if records.iter().any(|r| r.carnet.trim() == record.carnet.trim()) {
+                    continue;
+                }
+                let mut marks = vec![None; sessions.len()];
+                for (mark, &column) in record.marks.iter().zip(&columns) {
+                    marks[column] = *mark;
+                }
+                records.push(StudentAttendance { carnet: record.carnet.clone(), marks });
+            }
+        }
+
+        AttendanceSheet { sessions, records }
+    }
+
+    /// Porcentaje de asistencia de cada estudiante de `table`, emparejado por carnet.
+    pub fn percentages_for(&self, table: &AcademicTable, policy: &AttendancePolicy) -> Vec<Option<f32>> {
+        table.records.iter().map(|student| {
+            self.records.iter()
+                .find(|r| r.carnet.trim() == student.carnet.trim())
+                .and_then(|r| summarize(r, policy).percent)
+        }).collect()
+    }
+}
+
+fn summarize(record: &StudentAttendance, policy: &AttendancePolicy) -> AttendanceSummary {
+    let count = |mark: AttendanceMark| record.marks.iter().filter(|m| **m == Some(mark)).count();
+    let (present, absent, late, excused) = (
+        count(AttendanceMark::Present),
+        count(AttendanceMark::Absent),
+        count(AttendanceMark::Late),
+        count(AttendanceMark::Excused),
+    );
+
+    // Las ausencias justificadas no cuentan en contra ni a favor
+    let counted = present + absent + late;
+    let percent = (counted > 0).then(|| (present as f32 + late as f32 * policy.late_weight) / counted as f32 * 100.0);
+
+    AttendanceSummary { carnet: record.carnet.clone(), present, absent, late, excused, percent }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+    use crate::models::stats::GradeStats;
+    use crate::parser::parse_csv;
+    use crate::rules::{AcademicStatus, GradingPolicy};
+
+    const CSV: &str = "#,Carnet,Nombre,Sem 1,Sem 2,Sem 3,Sem 4\n\
+                       1,A1,Ana,P,P,T,J\n\
+                       2,A2,Luis,A,P,A,A\n\
+                       3,A3,Eva,,,,\n";
+
+    #[test]
+    fn test_attendance_percentages() {
+        let sheet = AttendanceSheet::try_from(parse_csv(CSV.as_bytes()).unwrap()).unwrap();
+        assert_eq!(sheet.sessions, vec!["Sem 1", "Sem 2", "Sem 3", "Sem 4"]);
+
+        let summaries = sheet.summaries(&AttendancePolicy::default());
+        assert_eq!((summaries[0].present, summaries[0].late, summaries[0].excused), (2, 1, 1));
+        assert_eq!(summaries[0].percent, Some(100.0));
+        assert_eq!(summaries[1].percent, Some(25.0));
+        assert_eq!(summaries[2].percent, None);
+
+        let half_late = AttendancePolicy { late_weight: 0.5, ..AttendancePolicy::default() };
+        assert_eq!(sheet.summaries(&half_late)[0].percent, Some(2.5 / 3.0 * 100.0));
+    }
+
+    #[test]
+    fn test_low_attendance_fails_student() {
+        let sheet = AttendanceSheet::try_from(parse_csv(CSV.as_bytes()).unwrap()).unwrap();
+        let table = fixtures::table(&["Prueba 1", "Prueba 2"], &[
+            ("A1", "IMS", &["18/20", "NP"]),
+            ("A2", "IMS", &["18/20", "NP"]),
+            ("A3", "IMS", &["18/20", "NP"]),
+        ]);
+        let policy = GradingPolicy {
+            attendance: AttendancePolicy { min_percent: Some(75.0), ..AttendancePolicy::default() },
+            ..GradingPolicy::default()
+        };
+
+        let students = GradeStats::with_attendance(&table, &policy, Some(&sheet)).student_summaries();
+        assert_eq!(students[0].status, AcademicStatus::OnTrack);
+        assert_eq!(students[1].status, AcademicStatus::Failed);
+        assert_eq!(students[1].attendance_percent, Some(25.0));
+        // Sin registros de asistencia no se reprueba por inasistencia
+        assert_eq!(students[2].status, AcademicStatus::OnTrack);
+
+        let without = GradeStats::with_policy(&table, &policy).student_summaries();
+        assert_eq!(without[1].status, AcademicStatus::OnTrack);
+    }
+}
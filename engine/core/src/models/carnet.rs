@@ -0,0 +1,124 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::EngineError;
+
+/// Carnet ya validado: "23-A0201" es ingreso 2023, código "A", secuencia 201.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct Carnet {
+    pub value: String,
+    pub admission_year: u16,
+    pub sequence: u32,
+    /// Letters in the carnet (program or campus code), if the pattern has any.
+    pub code: Option<String>,
+}
+
+/// Plantillas aceptadas, probadas en orden. En cada plantilla:
+/// `Y` dígito del año (2 o 4), `N` dígito de la secuencia, `L` letra, y cualquier otro
+/// carácter debe aparecer tal cual (`\` antes de Y, N o L para usarlas literalmente).
+/// Ej.: "YY-LNNNN", "YYYYNNNN", `U\LSA/YYYY/NNN`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct CarnetFormat {
+    pub patterns: Vec<String>,
+    /// Added to two-digit years.
+    pub century: u16,
+}
+
+impl Default for CarnetFormat {
+    fn default() -> Self {
+        Self {
+            patterns: vec!["YY-LNNNN".to_string(), "YYLNNNN".to_string(), "YYYY-NNNN".to_string()],
+            century: 2000,
+        }
+    }
+}
+
+impl CarnetFormat {
+    pub fn parse(&self, value: &str) -> Result<Carnet, EngineError> {
+        let value = value.trim();
+        self.patterns.iter()
+            .find_map(|pattern| match_pattern(pattern, value, self.century))
+            .ok_or_else(|| EngineError::InvalidCarnet(value.to_string()))
+    }
+}
+
+impl FromStr for Carnet {
+    type Err = EngineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CarnetFormat::default().parse(s)
+    }
+}
+
+enum Slot {
+    Year,
+    Sequence,
+    Letter,
+    Literal(char),
+}
+
+fn slots(pattern: &str) -> Vec<Slot> {
+    let mut slots = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        slots.push(match c {
+            '\\' => Slot::Literal(chars.next().unwrap_or('\\')),
+            'Y' => Slot::Year,
+            'N' => Slot::Sequence,
+            'L' => Slot::Letter,
+            c => Slot::Literal(c),
+        });
+    }
+    slots
+}
+
+fn match_pattern(pattern: &str, value: &str, century: u16) -> Option<Carnet> {
+    let slots = slots(pattern);
+    let chars: Vec<char> = value.chars().collect();
+    if slots.len() != chars.len() {
+        return None;
+    }
+
+    let (mut year, mut sequence, mut code) = (String::new(), String::new(), String::new());
+    for (slot, c) in slots.iter().zip(&chars) {
+        match slot {
+            Slot::Year if c.is_ascii_digit() => year.push(*c),
+            Slot::Sequence if c.is_ascii_digit() => sequence.push(*c),
+            Slot::Letter if c.is_alphabetic() => code.push(c.to_ascii_uppercase()),
+            Slot::Literal(literal) if literal.eq_ignore_ascii_case(c) => {}
+            _ => return None,
+        }
+    }
+
+    let year: u16 = year.parse().ok()?;
+    Some(Carnet {
+        value: value.to_uppercase(),
+        admission_year: if year < 100 { century + year } else { year },
+        sequence: sequence.parse().ok()?,
+        code: (!code.is_empty()).then_some(code),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_carnets() {
+        let carnet: Carnet = "23-A0201".parse().unwrap();
+        assert_eq!(carnet.admission_year, 2023);
+        assert_eq!(carnet.sequence, 201);
+        assert_eq!(carnet.code.as_deref(), Some("A"));
+        assert_eq!("19b0007".parse::<Carnet>().unwrap().value, "19B0007");
+
+        assert!(matches!("23-A02".parse::<Carnet>(), Err(EngineError::InvalidCarnet(_))));
+        assert!("23-10201".parse::<Carnet>().is_err());
+
+        let custom = CarnetFormat { patterns: vec!["U\\LSA/YYYY/NNN".to_string()], century: 2000 };
+        let carnet = custom.parse("ulsa/2021/045").unwrap();
+        assert_eq!((carnet.admission_year, carnet.sequence, carnet.code), (2021, 45, None));
+    }
+}
@@ -1,19 +1,27 @@
 use serde::{Deserialize, Serialize};
 
 use crate::api::AcademicStatus;
+use crate::scale::{ConvertedGrade, GradeBucket};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
 pub struct StudentSummary {
     pub id : String,
     pub name: String,
     pub accumulated_score: Option<f32>,
     pub lost_points: Option<f32>,
+    pub extra_credit: Option<f32>,     // Ya incluido en accumulated_score
     pub percentile: Option<f32>,
     pub std_dev: Option<f32>,
     pub status: AcademicStatus,
+    pub attendance_percent: Option<f32>, // None si no se cargó asistencia
+    pub converted_grade: Option<ConvertedGrade>, // Según la escala del reglamento
+    pub projected_final: Option<f32>,  // Proyección por tendencia, complementa a `status`
+    pub pass_probability: Option<f32>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
 pub struct EvaluationSummary {
     pub id: String,
     pub name: String,
@@ -29,11 +37,14 @@ pub struct EvaluationSummary {
     pub missing_count: usize,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
 pub struct ClassSummary {
     pub student_count: usize,
 
     pub acumulated_points: Option<f32>,
+    pub extra_credit_points: Option<f32>,   // Puntos extra disponibles, fuera de acumulated_points
+    pub extra_credit_average: Option<f32>,  // Promedio de crédito extra entre quienes lo obtuvieron
     pub overall_average: Option<f32>,
     pub overall_std_dev: Option<f32>,
 
@@ -43,10 +54,12 @@ pub struct ClassSummary {
     pub on_track_count: usize,
     pub warning_count: usize,
     pub critical_count: usize,
+    pub grade_distribution: Vec<GradeBucket>,    // Vacío si el reglamento no define escala
 }   
 
 
 #[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
 pub struct GradebookSummary {
     pub students: Vec<StudentSummary>,
     pub evaluations: Vec<EvaluationSummary>,
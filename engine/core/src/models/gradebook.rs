@@ -3,23 +3,28 @@ use crate::error::EngineError;
 use crate::models::raw::RawTable;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
 pub struct AcademicTable {
     pub evaluations: Vec<String>, // Nombres de las columnas de notas
     pub records: Vec<StudentRecord>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
 pub struct StudentRecord {
     pub carnet: String,
     pub name: String,
     pub email: String,
     pub group: String,            // "IMS", "ICE", etc.
+    #[serde(default)]
+    pub section: Option<String>,  // Sección de origen al unir varios archivos
     pub grades: Vec<GradeValue>,  // Mapeado 1:1 con 'evaluations'
     pub final_grade: GradeValue,  // El campo NP
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "status", content = "value")]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
 pub enum GradeValue {
     Numeric(f32),
     Fraction { obtained: f32, total: f32 },
@@ -86,6 +91,7 @@ impl TryFrom<RawTable> for AcademicTable {
                 name,
                 email,
                 group,
+                section: None,
                 grades,
                 final_grade,
             });
@@ -98,6 +104,57 @@ impl TryFrom<RawTable> for AcademicTable {
     }
 }
 
+/// Igual que en la hoja: "9/10", "87", "RM", "NP"; vuelve a leerse igual con el importador.
+impl std::fmt::Display for GradeValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GradeValue::Numeric(v) => write!(f, "{}", v),
+            GradeValue::Fraction { obtained, total } => write!(f, "{}/{}", obtained, total),
+            GradeValue::Withdrawn => write!(f, "RM"),
+            GradeValue::Absent => write!(f, "NP"),
+            GradeValue::Label(label) => write!(f, "{}", label),
+        }
+    }
+}
+
+impl AcademicTable {
+    /// Categoría de cada evaluación, derivada del nombre sin el número final
+    /// ("Prueba 1" -> "Prueba", "Tarea 2b" -> "Tarea").
+    pub fn evaluation_categories(&self) -> Vec<String> {
+        self.evaluations.iter().map(|name| evaluation_category(name)).collect()
+    }
+
+    /// Grupos/carreras presentes en la tabla, en orden de aparición.
+    pub fn groups(&self) -> Vec<String> {
+        let mut groups: Vec<String> = Vec::new();
+        for record in &self.records {
+            if !groups.contains(&record.group) {
+                groups.push(record.group.clone());
+            }
+        }
+        groups
+    }
+
+    /// Sub-tabla con las mismas evaluaciones y sólo los estudiantes del grupo indicado.
+    pub fn filter_group(&self, group: &str) -> AcademicTable {
+        AcademicTable {
+            evaluations: self.evaluations.clone(),
+            records: self.records.iter().filter(|r| r.group == group).cloned().collect(),
+        }
+    }
+}
+
+pub fn evaluation_category(name: &str) -> String {
+    let trimmed = name.trim();
+    // Quitar el último token si lleva número ("Prueba 1", "Tarea 2b", "Quiz #3", "Prueba1")
+    let base = match trimmed.rsplit_once(char::is_whitespace) {
+        Some((head, last)) if last.chars().any(|c| c.is_ascii_digit()) => head,
+        _ => trimmed.trim_end_matches(|c: char| c.is_ascii_digit()),
+    };
+    let base = base.trim_end_matches(|c: char| c.is_whitespace() || "-_#.".contains(c));
+    if base.is_empty() { trimmed.to_string() } else { base.to_string() }
+}
+
 /// Lógica central para interpretar el contenido de las celdas de la universidad
 fn parse_cell(cell: &Option<String>) -> GradeValue {
     let s = match cell {
@@ -127,6 +184,28 @@ fn parse_cell(cell: &Option<String>) -> GradeValue {
     GradeValue::Label(s)
 }
 
+/// Tablas sintéticas para los tests de los módulos de análisis.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::*;
+
+    /// Builds a table from `(carnet, group, cells)` rows, parsing each cell like the importer does.
+    pub(crate) fn table(evaluations: &[&str], rows: &[(&str, &str, &[&str])]) -> AcademicTable {
+        AcademicTable {
+            evaluations: evaluations.iter().map(|e| e.to_string()).collect(),
+            records: rows.iter().map(|(carnet, group, cells)| StudentRecord {
+                carnet: carnet.to_string(),
+                name: format!("Estudiante {}", carnet),
+                email: format!("{}@est.ulsa", carnet),
+                group: group.to_string(),
+                section: None,
+                grades: cells.iter().map(|c| parse_cell(&Some(c.to_string()))).collect(),
+                final_grade: GradeValue::Absent,
+            }).collect(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,6 +291,16 @@ mod tests {
         assert_eq!(s3.name, "ALEJANDRO COTTO");
     }
 
+    #[test]
+    fn test_evaluation_category() {
+        assert_eq!(evaluation_category("Prueba 1"), "Prueba");
+        assert_eq!(evaluation_category("Tarea 2b"), "Tarea");
+        assert_eq!(evaluation_category("Quiz #3"), "Quiz");
+        assert_eq!(evaluation_category("Prueba1"), "Prueba");
+        assert_eq!(evaluation_category("Examen Final"), "Examen Final");
+        assert_eq!(evaluation_category("2024"), "2024");
+    }
+
     #[test]
     fn test_parse_cell_logic() {
         assert_eq!(parse_cell(&Some("9/10".to_string())), GradeValue::Fraction { obtained: 9.0, total: 10.0 });
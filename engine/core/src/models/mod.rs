@@ -1,3 +1,5 @@
+pub mod attendance;
+pub mod carnet;
 pub mod domain;
 pub mod gradebook;
 pub mod raw;
@@ -1,22 +1,33 @@
 use serde::{Deserialize, Serialize};
 
+use crate::analysis::projection::{compute_projections, StudentProjection};
+use crate::makeup::{makeup_summary, MakeupGrade};
+use crate::models::attendance::AttendanceSheet;
 use crate::models::domain::{ClassSummary, EvaluationSummary, GradebookSummary, StudentSummary};
 use crate::models::gradebook::{AcademicTable, GradeValue};
-use crate::rules::AcademicStatus;
+use crate::rules::{AcademicStatus, GradingPolicy};
+use crate::scale::ConvertedGrade;
 
 pub struct GradeStats<'a> {
     table: &'a AcademicTable,
+    policy: GradingPolicy,
 
     student_scores: Vec<Option<f32>>,
     student_std: Vec<Option<f32>>,
     student_percentiles: Vec<Option<f32>>,
     student_lost_points : Vec<Option<f32>>,
+    student_extra_credit: Vec<Option<f32>>,
+    student_projections: Vec<StudentProjection>,
+    student_attendance: Vec<Option<f32>>,
+    /// Nota final tras la reposición; con ella el estado ya no depende de puntos en juego.
+    student_makeup: Vec<Option<f32>>,
 
     evaluation_averages: Vec<Option<f32>>,
     evaluation_std: Vec<Option<f32>>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
 pub struct GradeStatsOwned {
     pub students: Vec<StudentSummary>,
     pub evaluations: Vec<EvaluationSummary>,
@@ -26,11 +37,26 @@ pub struct GradeStatsOwned {
 
 impl From<&AcademicTable> for GradeStatsOwned {
     fn from(table: &AcademicTable) -> Self {
-        let grades_stats = GradeStats::new(table);
+        GradeStatsOwned::with_policy(table, &GradingPolicy::default())
+    }
+}
+
+impl GradeStatsOwned {
+    pub fn with_policy(table: &AcademicTable, policy: &GradingPolicy) -> Self {
+        Self::with_attendance(table, policy, None)
+    }
+
+    pub fn with_attendance(table: &AcademicTable, policy: &GradingPolicy, attendance: Option<&AttendanceSheet>) -> Self {
+        Self::from(&GradeStats::with_attendance(table, policy, attendance))
+    }
+}
+
+impl From<&GradeStats<'_>> for GradeStatsOwned {
+    fn from(stats: &GradeStats<'_>) -> Self {
         Self {
-            students: grades_stats.student_summaries(),
-            evaluations: grades_stats.evaluation_summaries(),
-            class: grades_stats.class_summary(),
+            students: stats.student_summaries(),
+            evaluations: stats.evaluation_summaries(),
+            class: stats.class_summary(),
         }
     }
 }
@@ -38,7 +64,20 @@ impl From<&AcademicTable> for GradeStatsOwned {
 
 impl<'a> GradeStats<'a> {
     pub fn new(table: &'a AcademicTable) -> Self {
-        let student_scores = compute_student_accumulated_scores(table);
+        Self::with_policy(table, &GradingPolicy::default())
+    }
+
+    pub fn with_policy(table: &'a AcademicTable, policy: &GradingPolicy) -> Self {
+        Self::with_attendance(table, policy, None)
+    }
+
+    /// Igual que `with_policy`, pero la asistencia bajo el mínimo del reglamento reprueba.
+    pub fn with_attendance(table: &'a AcademicTable, policy: &GradingPolicy, attendance: Option<&AttendanceSheet>) -> Self {
+        // Acumulado y puntos perdidos ya con las reglas por categoría del reglamento
+        let totals = policy.student_totals(table);
+        let student_scores: Vec<Option<f32>> = totals.iter().map(|t| t.accumulated).collect();
+        let student_lost_points: Vec<Option<f32>> = totals.iter().map(|t| t.lost).collect();
+        let student_extra_credit: Vec<Option<f32>> = totals.iter().map(|t| t.extra_credit).collect();
         // Standard deviation for students remains relevant if we view it as deviation from mean class score? 
         // Or deviation of their own grades? 
         // compute_student_std was using normalized averages. 
@@ -49,21 +88,74 @@ impl<'a> GradeStats<'a> {
 
         let evaluation_averages = compute_evaluation_averages(table);
         let evaluation_std = compute_evaluation_std(table, &evaluation_averages);
-        let student_lost_points = compute_student_lost_points(table);
+        let student_projections = compute_projections(table, policy, &totals);
+        let student_attendance = match attendance {
+            Some(sheet) => sheet.percentages_for(table, &policy.attendance),
+            None => vec![None; table.records.len()],
+        };
+        let student_makeup = vec![None; table.records.len()];
         Self {
             table,
+            policy: policy.clone(),
             student_scores,
             student_std,
             student_percentiles,
             student_lost_points,
+            student_extra_credit,
+            student_projections,
+            student_attendance,
+            student_makeup,
             evaluation_averages,
             evaluation_std,
         }
     }
 
+    /// Aplica las notas de reposición de los estudiantes elegibles (ver `makeup_summary`): la nota
+    /// final reemplaza al acumulado y el estado se cierra en aprobado o reprobado.
+    pub fn with_makeup(mut self, grades: &[MakeupGrade]) -> Self {
+        if grades.is_empty() {
+            return self;
+        }
+        for outcome in makeup_summary(&self, grades).outcomes {
+            if let Some(i) = self.table.records.iter().position(|r| r.carnet == outcome.carnet) {
+                self.student_scores[i] = Some(outcome.final_score);
+                self.student_makeup[i] = Some(outcome.final_score);
+            }
+        }
+        self.student_percentiles = compute_student_percentiles(&self.student_scores);
+        self
+    }
+
+    pub(crate) fn table(&self) -> &'a AcademicTable {
+        self.table
+    }
+
+    pub(crate) fn policy(&self) -> &GradingPolicy {
+        &self.policy
+    }
+
+    fn fails_attendance(&self, student_idx: usize) -> bool {
+        let min_attendance = self.policy.attendance.min_percent;
+        min_attendance.zip(self.student_attendance[student_idx]).is_some_and(|(min, a)| a < min)
+    }
+
+    /// Estado con una nota final, cuando ya no quedan puntos en juego (tras la reposición).
+    pub(crate) fn closed_status(&self, student_idx: usize, final_score: f32) -> AcademicStatus {
+        if self.fails_attendance(student_idx) || final_score < self.policy.passing_score {
+            AcademicStatus::Failed
+        } else {
+            AcademicStatus::Approved
+        }
+    }
+
     pub fn academic_status(&self, student_idx: usize) -> AcademicStatus {
-        const TOTAL_COURSE_POINTS: f32 = 100.0;
-        const PASSING_SCORE: f32 = 70.0; // Ajusta según tu reglamento
+        // 0. La inasistencia reprueba sin importar las notas
+        if self.fails_attendance(student_idx) {
+            return AcademicStatus::Failed;
+        }
+        if let Some(final_score) = self.student_makeup[student_idx] {
+            return self.closed_status(student_idx, final_score);
+        }
 
         // 1. Obtener nota acumulada actual (lo que ya tiene en la bolsa)
         // Asumimos que calculate_total_score suma los numeradores
@@ -73,27 +165,30 @@ impl<'a> GradeStats<'a> {
         };
 
         // 2. Si ya cruzó la meta, Aprobado directo.
-        if current_score >= PASSING_SCORE {
+        let passing_score = self.policy.passing_score;
+        if current_score >= passing_score {
             return AcademicStatus::Approved;
         }
 
         // 3. Calcular "Puntos Perdidos" (La clave de la solución)
         // Iteramos sobre las tareas YA evaluadas y vemos cuántos puntos dejó ir.
-        let lost_points = self.calculate_lost_points(student_idx);
+        let lost_points = self.student_lost_points[student_idx].unwrap_or(0.0);
 
         // 4. Calcular el "Techo Máximo" (Max Possible Score)
         // Si el curso vale 100 y perdió 10, lo máximo que puede sacar es 90.
-        let max_possible_score = TOTAL_COURSE_POINTS - lost_points;
+        // El crédito extra ya está en current_score, así que también sube el techo.
+        let extra_credit = self.student_extra_credit[student_idx].unwrap_or(0.0);
+        let max_possible_score = self.policy.total_course_points - lost_points + extra_credit;
 
         // 5. Escenario: Matemáticamente Reprobado
         // Si su techo máximo es menor que la nota de pase (ej: Max 65 < 70)
-        if max_possible_score < PASSING_SCORE {
+        if max_possible_score < passing_score {
             return AcademicStatus::Failed;
         }
 
         // 6. Análisis de Proyección (Pressure)
         // ¿Cuántos puntos le faltan para llegar al 70?
-        let points_needed_to_pass = PASSING_SCORE - current_score;
+        let points_needed_to_pass = passing_score - current_score;
         
         // ¿Cuántos puntos quedan TODAVÍA en la mesa de juego?
         // Esto es: Su techo máximo - lo que ya tiene ganado.
@@ -113,23 +208,6 @@ impl<'a> GradeStats<'a> {
         }
     }
 
-    // --- NUEVA FUNCIÓN AUXILIAR ---
-    fn calculate_lost_points(&self, student_idx: usize) -> f32 {
-        let mut lost = 0.0;
-        
-        // Iteramos sobre las calificaciones del alumno
-        for (_eval_idx, grade) in self.table.records[student_idx].grades.iter().enumerate() {
-            if let GradeValue::Fraction { obtained, total } = grade {
-                if *total > 0.0 {
-                    lost += total - obtained;
-                }
-            }
-            // Si es None o es una nota vacía, NO ha perdido puntos.
-            // Asumimos que todavía puede sacar el 100% de esos puntos futuros.
-        }
-        lost
-    }
-
     pub fn student_summaries(&self) -> Vec<StudentSummary> {
         self.table.records.iter().enumerate().map(|(i, record)| {
             StudentSummary {
@@ -137,13 +215,31 @@ impl<'a> GradeStats<'a> {
                 name: record.name.clone(),
                 accumulated_score: self.student_scores[i],
                 lost_points: self.student_lost_points[i],
+                extra_credit: self.student_extra_credit[i],
                 percentile: self.student_percentiles[i],
                 std_dev: self.student_std[i],
                 status: self.academic_status(i),
+                attendance_percent: self.student_attendance[i],
+                converted_grade: self.converted_grade(i),
+                projected_final: self.student_makeup[i].or(self.student_projections[i].projected_final),
+                pass_probability: match self.student_makeup[i] {
+                    Some(_) if self.academic_status(i) == AcademicStatus::Approved => Some(1.0),
+                    Some(_) => Some(0.0),
+                    None => self.student_projections[i].pass_probability,
+                },
             }
         }).collect()
     }
 
+    pub fn converted_grade(&self, student_idx: usize) -> Option<ConvertedGrade> {
+        let scale = self.policy.grade_scale.as_ref()?;
+        scale.convert(self.student_scores[student_idx]?, self.policy.total_course_points)
+    }
+
+    pub fn student_projections(&self) -> &[StudentProjection] {
+        &self.student_projections
+    }
+
     pub fn evaluation_summaries(&self) -> Vec<EvaluationSummary> {
         self.table.evaluations.iter().enumerate().map(|(eval_idx, name)| {
             let mut highest_score = None;
@@ -232,12 +328,29 @@ impl<'a> GradeStats<'a> {
             None
         };
 
+        // El crédito extra no sube el total del curso: se reporta aparte
+        let extra_mask = self.policy.extra_credit_mask(self.table);
+        let evaluations = self.evaluation_summaries();
+        let extra_credit_points = evaluations.iter().zip(&extra_mask)
+            .filter(|(_, is_extra)| **is_extra)
+            .filter_map(|(eval, _)| eval.max_possible_score)
+            .reduce(|a, b| a + b);
+        let earned_extra: Vec<f32> = self.student_extra_credit.iter().flatten().copied().collect();
+        let extra_credit_average = if earned_extra.is_empty() {
+            None
+        } else {
+            Some(earned_extra.iter().sum::<f32>() / earned_extra.len() as f32)
+        };
+
         ClassSummary {
             student_count: self.table.records.len(),
-            acumulated_points: self.evaluation_summaries().iter()
-                .filter_map(|eval| eval.max_possible_score)
+            acumulated_points: evaluations.iter().zip(&extra_mask)
+                .filter(|(_, is_extra)| !**is_extra)
+                .filter_map(|(eval, _)| eval.max_possible_score)
                 .sum::<f32>()
                 .into(),
+            extra_credit_points,
+            extra_credit_average,
             overall_average,
             overall_std_dev,
             evaluation_count: self.table.evaluations.len(),
@@ -246,6 +359,9 @@ impl<'a> GradeStats<'a> {
             on_track_count,
             warning_count,
             critical_count,
+            grade_distribution: self.policy.grade_scale.as_ref()
+                .map(|scale| scale.distribution(&self.student_scores, self.policy.total_course_points))
+                .unwrap_or_default(),
         }
     
     }
@@ -263,7 +379,7 @@ impl<'a> GradeStats<'a> {
 
 
 /// Helper to return the raw value (e.g., 9 from 9/10)
-fn extract_raw_score(value: &GradeValue) -> Option<f32> {
+pub(crate) fn extract_raw_score(value: &GradeValue) -> Option<f32> {
     match value {
         GradeValue::Numeric(v) => Some(*v),
         GradeValue::Fraction { obtained, .. } => Some(*obtained),
@@ -271,23 +387,25 @@ fn extract_raw_score(value: &GradeValue) -> Option<f32> {
     }
 }
 
-fn compute_student_accumulated_scores(table: &AcademicTable) -> Vec<Option<f32>> {
-    table.records.iter().map(|record| {
-        let mut sum = 0.0;
-        let mut count = 0;
-
-        for grade in &record.grades {
-            if let Some(v) = extract_raw_score(grade) {
-                sum += v;
-                count += 1;
-            }
-        }
+/// Helper to return the score as a proportion of the evaluation max (e.g., 0.9 from 9/10).
+/// Numeric values need the column max, since they carry no total of their own.
+pub(crate) fn normalized_score(value: &GradeValue, column_max: Option<f32>) -> Option<f32> {
+    match value {
+        GradeValue::Fraction { obtained, total } if *total > 0.0 => Some(obtained / total),
+        GradeValue::Numeric(v) => column_max.filter(|m| *m > 0.0).map(|m| v / m),
+        _ => None,
+    }
+}
 
-        // If at least one grade is present, we return the sum.
-        // If all are absent/withdrawn, we might return None or 0.0?
-        // Old logic: if count == 0 { None }
-        // Let's stick to that.
-        if count == 0 { None } else { Some(sum) }
+/// Max possible score of each evaluation, discovered from the first Fraction in the column.
+pub(crate) fn compute_evaluation_max_scores(table: &AcademicTable) -> Vec<Option<f32>> {
+    (0..table.evaluations.len()).map(|eval_idx| {
+        table.records.iter()
+            .filter_map(|record| match record.grades.get(eval_idx) {
+                Some(GradeValue::Fraction { total, .. }) if *total > 0.0 => Some(*total),
+                _ => None,
+            })
+            .next()
     }).collect()
 }
 
@@ -389,24 +507,6 @@ fn compute_evaluation_std(table: &AcademicTable, avgs: &[Option<f32>]) -> Vec<Op
     }).collect()
 }
 
-fn compute_student_lost_points(table: &AcademicTable) -> Vec<Option<f32>> {
-    table.records.iter().map(|record| {
-        let mut lost = 0.0;
-        let mut has_data = false;
-
-        for grade in &record.grades {
-            if let GradeValue::Fraction { obtained, total } = grade {
-                if *total > 0.0 {
-                    lost += total - obtained;
-                    has_data = true;
-                }
-            }
-        }
-
-        if has_data { Some(lost) } else { None }
-    }).collect()
-}
-
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -427,6 +527,19 @@ mod tests {
     }
 
 
+    #[test]
+    fn test_extra_credit_raises_the_ceiling() {
+        // Base 55 con 40 perdidos y 10 de bono: 55 + 5 restantes + 10 = 70, todavía alcanzable
+        let table = crate::models::gradebook::fixtures::table(&["Prueba 1", "Bono"], &[
+            ("A1", "IMS", &["55/95", "10/10"]),
+        ]);
+        let policy = GradingPolicy { extra_credit: vec!["bono".to_string()], ..GradingPolicy::default() };
+        let stats = GradeStats::with_policy(&table, &policy);
+
+        assert_eq!(stats.student_summaries()[0].accumulated_score, Some(65.0));
+        assert_eq!(stats.academic_status(0), AcademicStatus::Critical);
+    }
+
     // --- NUEVO TEST DE INTEGRACIÓN (Datos Reales) ---
 
     #[test]
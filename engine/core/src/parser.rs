@@ -78,6 +78,90 @@ pub fn parse_excel(data: &[u8]) -> Result<(Vec<String>, RawTable), EngineError>
 }
 
 
+/// Lee una hoja por nombre (sin distinguir mayúsculas) o la primera. A diferencia de
+/// `parse_excel`, conserva los encabezados vacíos para que queden alineados con las filas.
+pub fn parse_sheet(data: &[u8], sheet: Option<&str>) -> Result<RawTable, EngineError> {
+    if data.is_empty() {
+        return Err(EngineError::EmptyInput);
+    }
+
+    let mut workbook: Sheets<_> = open_workbook_auto_from_rs(Cursor::new(data))
+        .map_err(|_| EngineError::ExcelError)?;
+
+    let names = workbook.sheet_names();
+    let sheet_name = match sheet {
+        Some(wanted) => names.iter().find(|n| n.trim().eq_ignore_ascii_case(wanted.trim())),
+        None => names.first(),
+    }.ok_or(EngineError::EmptyInput)?.clone();
+
+    let range = workbook.worksheet_range(&sheet_name)
+        .map_err(|_| EngineError::ExcelError)?;
+
+    let rows = range.rows().map(|row| row.iter().map(|cell| cell.to_string()).collect());
+    aligned_table(rows).ok_or(EngineError::ExcelError)
+}
+
+/// CSV con separador `,` o `;` (el que más aparezca en la primera línea) y campos entre comillas.
+pub fn parse_csv(data: &[u8]) -> Result<RawTable, EngineError> {
+    if data.is_empty() {
+        return Err(EngineError::EmptyInput);
+    }
+    let text = std::str::from_utf8(data).map_err(|_| EngineError::Utf8Error)?;
+    let text = text.trim_start_matches('\u{feff}');
+
+    let first_line = text.lines().next().unwrap_or_default();
+    let delimiter = if first_line.matches(';').count() > first_line.matches(',').count() { ';' } else { ',' };
+
+    let mut rows = Vec::new();
+    for (line_no, line) in text.lines().enumerate() {
+        rows.push(split_csv_line(line, delimiter)
+            .ok_or_else(|| EngineError::CsvParseError(format!("unterminated quote on line {}", line_no + 1)))?);
+    }
+    aligned_table(rows.into_iter())
+        .ok_or_else(|| EngineError::CsvParseError("no CARNET header row".to_string()))
+}
+
+fn split_csv_line(line: &str, delimiter: char) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if in_quotes {
+        return None;
+    }
+    fields.push(field);
+    Some(fields)
+}
+
+/// Busca la fila con "CARNET" en cualquier columna y toma las filas siguientes hasta el
+/// primer carnet vacío. Encabezados y celdas comparten índices.
+fn aligned_table(rows: impl Iterator<Item = Vec<String>>) -> Option<RawTable> {
+    let mut rows = rows.map(|row| row.iter().map(|c| c.trim().to_string()).collect::<Vec<_>>());
+
+    let headers = rows.by_ref()
+        .find(|row| row.iter().any(|c| c.eq_ignore_ascii_case("CARNET")))?;
+    let carnet_idx = headers.iter().position(|c| c.eq_ignore_ascii_case("CARNET")).unwrap_or_default();
+
+    let rows = rows
+        .take_while(|row| row.get(carnet_idx).is_some_and(|c| !c.is_empty()))
+        .map(|row| row.into_iter().map(|c| if c.is_empty() { None } else { Some(c) }).collect())
+        .collect();
+
+    Some(RawTable { headers, rows })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,4 +179,17 @@ mod tests {
         assert!(!table.headers.is_empty());
         assert!(!table.rows.is_empty());
     }
+
+    #[test]
+    fn test_parse_csv() {
+        let data = "Asistencia;;\n#;Carnet;Nombre;S1;S2\n1;23-A0201;\"Pérez; Ana\";P;A\n2;23-A0202;Luis;;T\n;;;;\n";
+        let table = parse_csv(data.as_bytes()).unwrap();
+
+        assert_eq!(table.headers, vec!["#", "Carnet", "Nombre", "S1", "S2"]);
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.rows[0][2].as_deref(), Some("Pérez; Ana"));
+        assert_eq!(table.rows[1][3], None);
+
+        assert!(matches!(parse_csv(b"Carnet,\"abierto\n"), Err(EngineError::CsvParseError(_))));
+    }
 }
\ No newline at end of file
@@ -1,10 +1,311 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::gradebook::{AcademicTable, GradeValue, StudentRecord};
+use crate::models::stats::{compute_evaluation_max_scores, normalized_score};
+use crate::makeup::MakeupPolicy;
+use crate::models::attendance::AttendancePolicy;
+use crate::scale::GradeScale;
+
+pub const TOTAL_COURSE_POINTS: f32 = 100.0;
+pub const PASSING_SCORE: f32 = 70.0; // Ajusta según tu reglamento
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
 pub enum AcademicStatus {
     Approved,           // Ya superó el 60 (Matemáticamente a salvo)
     OnTrack,            // No llega a 60 aún, pero su rendimiento actual es aprobatorio
     Warning,            // Puede pasar, pero necesita mejorar su rendimiento en lo que falta
     Critical,           // Matemáticamente posible, pero necesita notas casi perfectas
     Failed,             // Matemáticamente imposible llegar a 60 (Ej: tiene 30 y faltan 20 pts)
-}
\ No newline at end of file
+}
+
+/// Reglamento de evaluación del curso.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct GradingPolicy {
+    pub total_course_points: f32,
+    pub passing_score: f32,
+    #[serde(default)]
+    pub categories: Vec<CategoryRule>,
+    /// Evaluations (by name or category) that add points without raising the course total
+    /// and never count as lost points.
+    #[serde(default)]
+    pub extra_credit: Vec<String>,
+    /// Most extra credit points a student can earn overall.
+    #[serde(default)]
+    pub extra_credit_cap: Option<f32>,
+    /// Official scale for reporting; None keeps only the numeric score.
+    #[serde(default)]
+    pub grade_scale: Option<GradeScale>,
+    /// Minimum attendance; failing it marks the student Failed.
+    #[serde(default)]
+    pub attendance: AttendancePolicy,
+    /// Who may sit the make-up exam and what it replaces.
+    #[serde(default)]
+    pub makeup: MakeupPolicy,
+}
+
+impl Default for GradingPolicy {
+    fn default() -> Self {
+        Self {
+            total_course_points: TOTAL_COURSE_POINTS,
+            passing_score: PASSING_SCORE,
+            categories: Vec::new(),
+            extra_credit: Vec::new(),
+            extra_credit_cap: None,
+            grade_scale: None,
+            attendance: AttendancePolicy::default(),
+            makeup: MakeupPolicy::default(),
+        }
+    }
+}
+
+/// Reglas de agregación de una categoría ("Quiz", "Prueba", ...), aplicadas en este orden:
+/// drop_lowest, best_of, replace_lowest_with y max_points.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct CategoryRule {
+    /// Category name as derived by `evaluation_category` (case-insensitive).
+    pub category: String,
+    /// Drop the k lowest graded evaluations ("se elimina el quiz más bajo").
+    #[serde(default)]
+    pub drop_lowest: usize,
+    /// Keep only the best n graded evaluations.
+    #[serde(default)]
+    pub best_of: Option<usize>,
+    /// Evaluation whose percentage replaces the lowest of the category when it is higher
+    /// ("el examen final reemplaza el parcial más bajo").
+    #[serde(default)]
+    pub replace_lowest_with: Option<String>,
+    /// Cap on the points the category can contribute.
+    #[serde(default)]
+    pub max_points: Option<f32>,
+}
+
+/// Puntos acumulados y perdidos de un estudiante, ya con las reglas aplicadas.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct StudentTotals {
+    pub accumulated: Option<f32>, // Incluye el crédito extra
+    pub lost: Option<f32>,
+    pub extra_credit: Option<f32>,
+}
+
+/// Nota que cuenta para el acumulado: (obtenido, total si es fracción).
+type CountedScore = Option<(f32, Option<f32>)>;
+
+impl GradingPolicy {
+    fn rule_for(&self, category: &str) -> Option<&CategoryRule> {
+        self.categories.iter().find(|r| r.category.eq_ignore_ascii_case(category))
+    }
+
+    /// `true` for each evaluation marked as extra credit.
+    pub fn extra_credit_mask(&self, table: &AcademicTable) -> Vec<bool> {
+        table.evaluations.iter().zip(table.evaluation_categories()).map(|(name, category)| {
+            self.extra_credit.iter().any(|marked| {
+                marked.trim().eq_ignore_ascii_case(name.trim()) || marked.trim().eq_ignore_ascii_case(&category)
+            })
+        }).collect()
+    }
+
+    pub(crate) fn student_totals(&self, table: &AcademicTable) -> Vec<StudentTotals> {
+        let categories = table.evaluation_categories();
+        let max_scores = compute_evaluation_max_scores(table);
+        let extra_mask = self.extra_credit_mask(table);
+
+        table.records.iter()
+            .map(|record| self.record_totals(table, record, &categories, &max_scores, &extra_mask))
+            .collect()
+    }
+
+    /// Totales de un solo registro (por ejemplo, una copia con notas modificadas) con las
+    /// columnas de `table`.
+    pub(crate) fn totals_for(&self, table: &AcademicTable, record: &StudentRecord) -> StudentTotals {
+        let categories = table.evaluation_categories();
+        let max_scores = compute_evaluation_max_scores(table);
+        self.record_totals(table, record, &categories, &max_scores, &self.extra_credit_mask(table))
+    }
+
+    fn record_totals(
+        &self,
+        table: &AcademicTable,
+        record: &StudentRecord,
+        categories: &[String],
+        max_scores: &[Option<f32>],
+        extra_mask: &[bool],
+    ) -> StudentTotals {
+        let mut counted: Vec<CountedScore> = record.grades.iter().map(|grade| match grade {
+            GradeValue::Fraction { obtained, total } => Some((*obtained, Some(*total).filter(|t| *t > 0.0))),
+            GradeValue::Numeric(v) => Some((*v, None)),
+            _ => None,
+        }).collect();
+        let proportion = |e: usize| normalized_score(&record.grades[e], max_scores[e]);
+
+        // El crédito extra se aparta antes de las reglas por categoría
+        let mut extra_sum: Option<f32> = None;
+        for (e, is_extra) in extra_mask.iter().enumerate() {
+            if let (true, Some((obtained, _))) = (*is_extra, counted[e]) {
+                extra_sum = Some(extra_sum.unwrap_or(0.0) + obtained);
+                counted[e] = None;
+            }
+        }
+        let extra_credit = extra_sum.map(|sum| match self.extra_credit_cap {
+            Some(cap) => sum.min(cap),
+            None => sum,
+        });
+
+        let mut excess = 0.0;
+        for (category, rule) in self.categories_in(categories) {
+            let members: Vec<usize> = (0..counted.len())
+                .filter(|&e| categories[e] == category && !extra_mask[e])
+                .collect();
+            apply_drops(rule, &members, &mut counted, proportion);
+
+            if let Some(name) = &rule.replace_lowest_with {
+                let replacement = table.evaluations.iter().position(|e| e.trim().eq_ignore_ascii_case(name.trim()));
+                if let Some((r, p_r)) = replacement.and_then(|r| counted[r].and(proportion(r)).map(|p| (r, p))) {
+                    let lowest = members.iter().copied()
+                        .filter(|&e| e != r && counted[e].is_some())
+                        .filter_map(|e| proportion(e).map(|p| (e, p)))
+                        .min_by(|a, b| a.1.total_cmp(&b.1));
+                    if let Some((e, p_low)) = lowest.filter(|(_, p)| *p < p_r) {
+                        let max = counted[e].and_then(|c| c.1).or(max_scores[e]).unwrap_or(0.0);
+                        counted[e] = counted[e].map(|(obtained, total)| (obtained + (p_r - p_low) * max, total));
+                    }
+                }
+            }
+
+            if let Some(cap) = rule.max_points {
+                let sum: f32 = members.iter().filter_map(|&e| counted[e].map(|c| c.0)).sum();
+                excess += (sum - cap).max(0.0);
+            }
+        }
+
+        let scores: Vec<&(f32, Option<f32>)> = counted.iter().flatten().collect();
+        let base = if scores.is_empty() {
+            None
+        } else {
+            Some(scores.iter().map(|c| c.0).sum::<f32>() - excess)
+        };
+        let accumulated = match (base, extra_credit) {
+            (None, None) => None,
+            (base, extra) => Some(base.unwrap_or(0.0) + extra.unwrap_or(0.0)),
+        };
+
+        let fractions: Vec<f32> = scores.iter().filter_map(|(obtained, total)| total.map(|t| t - obtained)).collect();
+        let lost = if fractions.is_empty() { None } else { Some(fractions.iter().sum()) };
+
+        StudentTotals { accumulated, lost, extra_credit }
+    }
+
+    /// Categorías presentes en la tabla que tienen regla, sin repetir.
+    fn categories_in<'p>(&'p self, categories: &[String]) -> Vec<(String, &'p CategoryRule)> {
+        let mut seen: Vec<(String, &CategoryRule)> = Vec::new();
+        for category in categories {
+            if seen.iter().any(|(c, _)| c == category) {
+                continue;
+            }
+            if let Some(rule) = self.rule_for(category) {
+                seen.push((category.clone(), rule));
+            }
+        }
+        seen
+    }
+}
+
+/// Drops the lowest graded members (by percentage) according to `drop_lowest` and `best_of`.
+/// At least one graded evaluation always counts.
+fn apply_drops(
+    rule: &CategoryRule,
+    members: &[usize],
+    counted: &mut [CountedScore],
+    proportion: impl Fn(usize) -> Option<f32>,
+) {
+    let mut graded: Vec<(usize, Option<f32>)> = members.iter().copied()
+        .filter(|&e| counted[e].is_some())
+        .map(|e| (e, proportion(e)))
+        .collect();
+
+    let by_best_of = rule.best_of.map_or(0, |n| graded.len().saturating_sub(n));
+    let to_drop = rule.drop_lowest.max(by_best_of).min(graded.len().saturating_sub(1));
+
+    // Las notas sin porcentaje conocido van al final y nunca se eliminan primero
+    graded.sort_by(|a, b| match (a.1, b.1) {
+        (Some(x), Some(y)) => x.total_cmp(&y),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    for (e, _) in graded.into_iter().take(to_drop) {
+        counted[e] = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+
+    fn sample_table() -> AcademicTable {
+        fixtures::table(&["Quiz 1", "Quiz 2", "Quiz 3", "Parcial 1", "Parcial 2", "Examen Final"], &[
+            ("A1", "IMS", &["2/10", "8/10", "9/10", "10/20", "16/20", "18/20"]),
+            ("A2", "IMS", &["10/10", "NP", "NP", "15/20", "NP", "NP"]),
+        ])
+    }
+
+    fn policy(categories: Vec<CategoryRule>) -> GradingPolicy {
+        GradingPolicy { categories, ..GradingPolicy::default() }
+    }
+
+    #[test]
+    fn test_default_policy_sums_everything() {
+        let totals = GradingPolicy::default().student_totals(&sample_table());
+        assert_eq!(totals[0], StudentTotals { accumulated: Some(63.0), lost: Some(27.0), extra_credit: None });
+        assert_eq!(totals[1], StudentTotals { accumulated: Some(25.0), lost: Some(5.0), extra_credit: None });
+    }
+
+    #[test]
+    fn test_drop_lowest_and_best_of() {
+        let drop = policy(vec![CategoryRule { category: "quiz".to_string(), drop_lowest: 1, ..Default::default() }]);
+        let totals = drop.student_totals(&sample_table());
+        // El 2/10 deja de contar, junto con sus 8 puntos perdidos
+        assert_eq!(totals[0], StudentTotals { accumulated: Some(61.0), lost: Some(19.0), extra_credit: None });
+        // Con una sola nota en la categoría no se elimina nada
+        assert_eq!(totals[1].accumulated, Some(25.0));
+
+        let best = policy(vec![CategoryRule { category: "Quiz".to_string(), best_of: Some(1), ..Default::default() }]);
+        assert_eq!(best.student_totals(&sample_table())[0].accumulated, Some(53.0));
+    }
+
+    #[test]
+    fn test_replace_lowest_and_cap() {
+        let replace = policy(vec![CategoryRule {
+            category: "Parcial".to_string(),
+            replace_lowest_with: Some("examen final".to_string()),
+            ..Default::default()
+        }]);
+        // Parcial 1 (50%) se reemplaza por el 90% del final: 10 -> 18
+        assert_eq!(replace.student_totals(&sample_table())[0], StudentTotals { accumulated: Some(71.0), lost: Some(19.0), extra_credit: None });
+
+        let cap = policy(vec![CategoryRule { category: "Quiz".to_string(), max_points: Some(15.0), ..Default::default() }]);
+        assert_eq!(cap.student_totals(&sample_table())[0].accumulated, Some(59.0));
+    }
+
+    #[test]
+    fn test_extra_credit() {
+        let table = fixtures::table(&["Prueba 1", "Bono 1", "Bono 2"], &[
+            ("A1", "IMS", &["40/50", "3/5", "4/5"]),
+            ("A2", "IMS", &["NP", "5/5", "NP"]),
+        ]);
+        let bonus = GradingPolicy {
+            extra_credit: vec!["bono".to_string()],
+            extra_credit_cap: Some(5.0),
+            ..GradingPolicy::default()
+        };
+        assert_eq!(bonus.extra_credit_mask(&table), vec![false, true, true]);
+
+        let totals = bonus.student_totals(&table);
+        // 7 puntos de bono, topados en 5; los 3 puntos no obtenidos no cuentan como perdidos
+        assert_eq!(totals[0], StudentTotals { accumulated: Some(45.0), lost: Some(10.0), extra_credit: Some(5.0) });
+        assert_eq!(totals[1], StudentTotals { accumulated: Some(5.0), lost: None, extra_credit: Some(5.0) });
+    }
+}
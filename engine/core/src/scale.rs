@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+
+/// Escala oficial para reportar la nota final.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+#[serde(tag = "type")]
+pub enum GradeScale {
+    /// A–F with +/- (A >= 93, A- >= 90, ...), with the matching GPA points.
+    Letter,
+    /// 0–4.0 GPA points, same cut-offs as `Letter`.
+    Gpa,
+    /// 0–10 with one decimal.
+    TenPoint,
+    /// "Excelente", "Muy bueno", "Bueno", "Regular", "Deficiente".
+    Qualitative,
+    /// User-defined table; bands can be in any order.
+    Custom { bands: Vec<GradeBand> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct GradeBand {
+    /// Lowest percentage of the course total (0–100) that earns this band.
+    pub min_percent: f32,
+    pub label: String,
+    #[serde(default)]
+    pub points: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct ConvertedGrade {
+    pub label: String,
+    pub points: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct GradeBucket {
+    pub label: String,
+    pub count: usize,
+}
+
+const LETTER_BANDS: [(f32, &str, f32); 12] = [
+    (93.0, "A", 4.0),
+    (90.0, "A-", 3.7),
+    (87.0, "B+", 3.3),
+    (83.0, "B", 3.0),
+    (80.0, "B-", 2.7),
+    (77.0, "C+", 2.3),
+    (73.0, "C", 2.0),
+    (70.0, "C-", 1.7),
+    (67.0, "D+", 1.3),
+    (63.0, "D", 1.0),
+    (60.0, "D-", 0.7),
+    (0.0, "F", 0.0),
+];
+
+const QUALITATIVE_BANDS: [(f32, &str); 5] = [
+    (90.0, "Excelente"),
+    (80.0, "Muy bueno"),
+    (70.0, "Bueno"),
+    (60.0, "Regular"),
+    (0.0, "Deficiente"),
+];
+
+impl GradeScale {
+    /// Bands from highest to lowest. For `TenPoint` there is one band per whole point,
+    /// used to build the distribution.
+    pub fn bands(&self) -> Vec<GradeBand> {
+        let mut bands: Vec<GradeBand> = match self {
+            GradeScale::Letter => LETTER_BANDS.iter()
+                .map(|(min, label, points)| GradeBand { min_percent: *min, label: label.to_string(), points: Some(*points) })
+                .collect(),
+            GradeScale::Gpa => LETTER_BANDS.iter()
+                .map(|(min, _, points)| GradeBand { min_percent: *min, label: format!("{:.1}", points), points: Some(*points) })
+                .collect(),
+            GradeScale::TenPoint => (0..=10).rev()
+                .map(|p| GradeBand { min_percent: p as f32 * 10.0, label: p.to_string(), points: Some(p as f32) })
+                .collect(),
+            GradeScale::Qualitative => QUALITATIVE_BANDS.iter()
+                .map(|(min, label)| GradeBand { min_percent: *min, label: label.to_string(), points: None })
+                .collect(),
+            GradeScale::Custom { bands } => bands.clone(),
+        };
+        bands.sort_by(|a, b| b.min_percent.total_cmp(&a.min_percent));
+        bands
+    }
+
+    /// Converts a score out of `total` points; scores above the total (extra credit) stay in the top band.
+    pub fn convert(&self, score: f32, total: f32) -> Option<ConvertedGrade> {
+        if total <= 0.0 {
+            return None;
+        }
+        let percent = score / total * 100.0;
+
+        if let GradeScale::TenPoint = self {
+            let points = ten_point(percent);
+            return Some(ConvertedGrade { label: format!("{:.1}", points), points: Some(points) });
+        }
+
+        self.band_for(percent).map(|band| ConvertedGrade { label: band.label, points: band.points })
+    }
+
+    /// Cuántos estudiantes caen en cada banda, de la más alta a la más baja.
+    pub fn distribution(&self, scores: &[Option<f32>], total: f32) -> Vec<GradeBucket> {
+        let bands = self.bands();
+        let mut counts = vec![0; bands.len()];
+
+        for score in scores.iter().flatten() {
+            if total <= 0.0 {
+                break;
+            }
+            let mut percent = score / total * 100.0;
+            // La banda sale de la nota ya redondeada, para que coincida con `convert` (9.0 va al 9)
+            if let GradeScale::TenPoint = self {
+                percent = ten_point(percent) * 10.0;
+            }
+            if let Some(idx) = bands.iter().position(|b| percent >= b.min_percent) {
+                counts[idx] += 1;
+            }
+        }
+
+        bands.into_iter().zip(counts)
+            .map(|(band, count)| GradeBucket { label: band.label, count })
+            .collect()
+    }
+
+    fn band_for(&self, percent: f32) -> Option<GradeBand> {
+        self.bands().into_iter().find(|b| percent >= b.min_percent)
+    }
+}
+
+/// Nota de 0 a 10 con un decimal.
+fn ten_point(percent: f32) -> f32 {
+    ((percent / 10.0).clamp(0.0, 10.0) * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets() {
+        let letter = GradeScale::Letter.convert(91.0, 100.0).unwrap();
+        assert_eq!(letter, ConvertedGrade { label: "A-".to_string(), points: Some(3.7) });
+        assert_eq!(GradeScale::Letter.convert(45.0, 100.0).unwrap().label, "F");
+        assert_eq!(GradeScale::Gpa.convert(84.0, 100.0).unwrap().label, "3.0");
+        assert_eq!(GradeScale::TenPoint.convert(87.0, 100.0).unwrap().points, Some(8.7));
+        // Crédito extra por encima del total se queda en el tope
+        assert_eq!(GradeScale::TenPoint.convert(104.0, 100.0).unwrap().points, Some(10.0));
+        assert_eq!(GradeScale::Qualitative.convert(72.0, 100.0).unwrap().label, "Bueno");
+        assert_eq!(GradeScale::Qualitative.convert(36.0, 40.0).unwrap().label, "Excelente");
+    }
+
+    #[test]
+    fn test_custom_scale_and_distribution() {
+        let scale = GradeScale::Custom {
+            bands: vec![
+                GradeBand { min_percent: 70.0, label: "Aprobado".to_string(), points: None },
+                GradeBand { min_percent: 0.0, label: "Reprobado".to_string(), points: None },
+            ],
+        };
+        assert_eq!(scale.convert(69.9, 100.0).unwrap().label, "Reprobado");
+
+        let distribution = scale.distribution(&[Some(80.0), Some(70.0), Some(20.0), None], 100.0);
+        assert_eq!(distribution, vec![
+            GradeBucket { label: "Aprobado".to_string(), count: 2 },
+            GradeBucket { label: "Reprobado".to_string(), count: 1 },
+        ]);
+
+        // Sin banda para notas bajas: no hay conversión
+        let partial = GradeScale::Custom {
+            bands: vec![GradeBand { min_percent: 50.0, label: "S".to_string(), points: None }],
+        };
+        assert_eq!(partial.convert(10.0, 100.0), None);
+    }
+
+    #[test]
+    fn test_ten_point_distribution_matches_convert() {
+        let scores = [Some(89.96), Some(87.0), Some(79.94)];
+        let labels: Vec<String> = scores.iter().flatten()
+            .map(|s| GradeScale::TenPoint.convert(*s, 100.0).unwrap().label)
+            .collect();
+        assert_eq!(labels, vec!["9.0", "8.7", "8.0"]);
+
+        let count = |label: &str| GradeScale::TenPoint.distribution(&scores, 100.0)
+            .into_iter().find(|b| b.label == label).unwrap().count;
+        assert_eq!((count("9"), count("8"), count("7")), (1, 2, 0));
+    }
+}
@@ -0,0 +1,145 @@
+//! Estado completo de una sesión, para guardarlo (IndexedDB, archivos `.pna`) y restaurarlo.
+//!
+//! Formato: `PNA` + versión del esquema (u16, little endian) + JSON compacto. Sólo se guardan
+//! los datos de entrada; las estadísticas se recalculan al cargar.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::EngineError;
+use crate::makeup::MakeupGrade;
+use crate::models::attendance::AttendanceSheet;
+use crate::models::gradebook::AcademicTable;
+use crate::models::stats::GradeStats;
+use crate::parser::{parse_excel, parse_sheet};
+use crate::rules::GradingPolicy;
+
+const MAGIC: &[u8; 3] = b"PNA";
+pub const STATE_VERSION: u16 = 1;
+
+/// Migración de la versión `n` a `n + 1`, sobre el JSON crudo; índice 0 migra de 1 a 2.
+type Migration = fn(&mut Value) -> Result<(), EngineError>;
+const MIGRATIONS: &[Migration] = &[];
+
+/// Nota libre del docente sobre un estudiante, una evaluación o una celda.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(from_wasm_abi))]
+pub struct Annotation {
+    #[serde(default)]
+    pub carnet: Option<String>,
+    #[serde(default)]
+    pub evaluation: Option<String>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineState {
+    pub description_headers: Vec<String>,
+    pub table: AcademicTable,
+    pub policy: GradingPolicy,
+    #[serde(default)]
+    pub attendance: Option<AttendanceSheet>,
+    #[serde(default)]
+    pub makeup_grades: Vec<MakeupGrade>,
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+}
+
+impl EngineState {
+    /// Sesión nueva desde un libro de Excel u hoja de cálculo, con el reglamento por defecto.
+    pub fn from_workbook(data: &[u8]) -> Result<Self, EngineError> {
+        let (description_headers, raw) = parse_excel(data)?;
+        let table = AcademicTable::try_from(raw)?;
+        // La asistencia puede venir en una hoja "Asistencia" del mismo libro
+        let attendance = parse_sheet(data, Some("Asistencia")).ok()
+            .and_then(|raw| AttendanceSheet::try_from(raw).ok());
+        Ok(Self {
+            description_headers,
+            table,
+            policy: GradingPolicy::default(),
+            attendance,
+            makeup_grades: Vec::new(),
+            annotations: Vec::new(),
+        })
+    }
+
+    /// Estadísticas con la asistencia y las notas de reposición ya aplicadas.
+    pub fn stats(&self) -> GradeStats<'_> {
+        GradeStats::with_attendance(&self.table, &self.policy, self.attendance.as_ref())
+            .with_makeup(&self.makeup_grades)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {
+        let body = serde_json::to_vec(self).map_err(|e| EngineError::InvalidState(e.to_string()))?;
+        let mut bytes = Vec::with_capacity(MAGIC.len() + 2 + body.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&STATE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&body);
+        Ok(bytes)
+    }
+
+    /// Lee cualquier versión anterior o igual a `STATE_VERSION`, migrándola en orden.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EngineError> {
+        if bytes.is_empty() {
+            return Err(EngineError::EmptyInput);
+        }
+        let (header, body) = bytes.split_at_checked(MAGIC.len() + 2)
+            .filter(|(header, _)| header.starts_with(MAGIC))
+            .ok_or_else(|| EngineError::InvalidState("not a PNA state file".to_string()))?;
+        let version = u16::from_le_bytes([header[3], header[4]]);
+
+        if version == 0 || version > STATE_VERSION {
+            return Err(EngineError::InvalidState(format!(
+                "unsupported state version {} (this build reads up to {})", version, STATE_VERSION
+            )));
+        }
+
+        let mut value: Value = serde_json::from_slice(body).map_err(|e| EngineError::InvalidState(e.to_string()))?;
+        for migration in &MIGRATIONS[(version - 1) as usize..] {
+            migration(&mut value)?;
+        }
+        serde_json::from_value(value).map_err(|e| EngineError::InvalidState(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+
+    fn state() -> EngineState {
+        EngineState {
+            description_headers: vec!["Cálculo I | Sección A".to_string()],
+            table: fixtures::table(&["Prueba 1", "Examen"], &[
+                ("A1", "IMS", &["9/10", "RM"]),
+                ("A2", "ICE", &["7.5", "NP"]),
+            ]),
+            policy: GradingPolicy { passing_score: 60.0, ..GradingPolicy::default() },
+            attendance: None,
+            makeup_grades: vec![MakeupGrade { carnet: "A2".to_string(), score: 81.5 }],
+            annotations: vec![Annotation { carnet: Some("A1".to_string()), evaluation: None, text: "Beca".to_string() }],
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bytes = state().to_bytes().unwrap();
+        assert_eq!(&bytes[..3], b"PNA");
+
+        let restored = EngineState::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.table.records[0].grades, state().table.records[0].grades);
+        assert_eq!(restored.table.records[1].grades, state().table.records[1].grades);
+        assert_eq!(restored.policy, state().policy);
+        assert_eq!(restored.makeup_grades, state().makeup_grades);
+        assert_eq!(restored.annotations, state().annotations);
+    }
+
+    #[test]
+    fn test_rejects_unknown_input() {
+        assert!(matches!(EngineState::from_bytes(b"PK\x03\x04"), Err(EngineError::InvalidState(_))));
+
+        let mut future = state().to_bytes().unwrap();
+        future[3..5].copy_from_slice(&(STATE_VERSION + 1).to_le_bytes());
+        assert!(matches!(EngineState::from_bytes(&future), Err(EngineError::InvalidState(_))));
+    }
+}
@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::EngineError;
+use crate::models::domain::GradebookSummary;
+use crate::models::gradebook::{AcademicTable, GradeValue};
+use crate::models::stats::{compute_evaluation_max_scores, extract_raw_score, GradeStats};
+use crate::rules::GradingPolicy;
+
+/// Curvas disponibles. Todos los valores están en puntos de la evaluación curvada.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(from_wasm_abi))]
+#[serde(tag = "type")]
+pub enum Curve {
+    /// Multiply every score so the evaluation mean becomes `target_mean`.
+    LinearToMean { target_mean: f32 },
+    /// max * sqrt(score / max).
+    SquareRoot,
+    /// Add `points` to every score, never above the evaluation max.
+    AddConstant { points: f32 },
+    /// Map each score's z-score onto a new mean and standard deviation.
+    ZScore { target_mean: f32, target_std_dev: f32 },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct CellChange {
+    pub row: usize,
+    pub column: usize,
+    pub carnet: String,
+    pub old_value: GradeValue,
+    pub new_value: GradeValue,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
+pub struct CurvePreview {
+    pub table: AcademicTable,
+    pub changes: Vec<CellChange>,
+    /// Stats and statuses recomputed over the curved table.
+    pub summary: GradebookSummary,
+}
+
+impl AcademicTable {
+    /// Aplica la curva a una sola evaluación y devuelve una tabla nueva; `self` no cambia.
+    pub fn apply_curve(&self, eval_idx: usize, curve: &Curve) -> Result<(AcademicTable, Vec<CellChange>), EngineError> {
+        if eval_idx >= self.evaluations.len() {
+            return Err(EngineError::InvalidEvaluation(eval_idx));
+        }
+        let column_max = compute_evaluation_max_scores(self)[eval_idx];
+
+        let scores: Vec<f32> = self.records.iter()
+            .filter_map(|r| r.grades.get(eval_idx).and_then(extract_raw_score))
+            .collect();
+        let mapping = CurveMapping::fit(curve, &scores)?;
+
+        let mut curved = self.clone();
+        let mut changes = Vec::new();
+
+        for (row, record) in curved.records.iter_mut().enumerate() {
+            let Some(grade) = record.grades.get_mut(eval_idx) else { continue };
+            let new_value = match grade {
+                GradeValue::Fraction { obtained, total } => GradeValue::Fraction {
+                    obtained: mapping.apply(*obtained, Some(*total))?,
+                    total: *total,
+                },
+                GradeValue::Numeric(v) => GradeValue::Numeric(mapping.apply(*v, column_max)?),
+                // Ausentes, retirados y etiquetas no se curvan
+                _ => continue,
+            };
+
+            if new_value != *grade {
+                changes.push(CellChange {
+                    row,
+                    column: eval_idx,
+                    carnet: record.carnet.clone(),
+                    old_value: grade.clone(),
+                    new_value: new_value.clone(),
+                });
+                *grade = new_value;
+            }
+        }
+
+        Ok((curved, changes))
+    }
+
+    /// Same as `apply_curve`, plus the summary recomputed through `GradeStats`.
+    pub fn preview_curve(&self, eval_idx: usize, curve: &Curve, policy: &GradingPolicy) -> Result<CurvePreview, EngineError> {
+        let (table, changes) = self.apply_curve(eval_idx, curve)?;
+        let summary = GradeStats::with_policy(&table, policy).summary();
+        Ok(CurvePreview { table, changes, summary })
+    }
+}
+
+/// Curva con sus parámetros ya ajustados a la columna.
+enum CurveMapping {
+    Scale(f32),
+    SquareRoot,
+    Shift(f32),
+    ZScore { mean: f32, std_dev: f32, target_mean: f32, target_std_dev: f32 },
+}
+
+impl CurveMapping {
+    fn fit(curve: &Curve, scores: &[f32]) -> Result<Self, EngineError> {
+        let count = scores.len() as f32;
+        let mean = (count > 0.0).then(|| scores.iter().sum::<f32>() / count);
+
+        match curve {
+            Curve::LinearToMean { target_mean } => {
+                let mean = mean.filter(|m| *m > 0.0)
+                    .ok_or_else(|| EngineError::InvalidTransform("evaluation mean is zero".to_string()))?;
+                Ok(CurveMapping::Scale(target_mean / mean))
+            }
+            Curve::SquareRoot => Ok(CurveMapping::SquareRoot),
+            Curve::AddConstant { points } => Ok(CurveMapping::Shift(*points)),
+            Curve::ZScore { target_mean, target_std_dev } => {
+                let mean = mean.ok_or_else(|| EngineError::InvalidTransform("evaluation has no scores".to_string()))?;
+                let std_dev = if scores.len() > 1 {
+                    (scores.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / (count - 1.0)).sqrt()
+                } else {
+                    0.0
+                };
+                if std_dev <= f32::EPSILON {
+                    return Err(EngineError::InvalidTransform("evaluation has no spread".to_string()));
+                }
+                Ok(CurveMapping::ZScore {
+                    mean,
+                    std_dev,
+                    target_mean: *target_mean,
+                    target_std_dev: *target_std_dev,
+                })
+            }
+        }
+    }
+
+    fn apply(&self, score: f32, max: Option<f32>) -> Result<f32, EngineError> {
+        let curved = match self {
+            CurveMapping::Scale(factor) => score * factor,
+            CurveMapping::SquareRoot => {
+                let max = max.filter(|m| *m > 0.0).ok_or_else(|| {
+                    EngineError::InvalidTransform("square root curve needs the evaluation max".to_string())
+                })?;
+                max * (score.max(0.0) / max).sqrt()
+            }
+            CurveMapping::Shift(points) => score + points,
+            CurveMapping::ZScore { mean, std_dev, target_mean, target_std_dev } => {
+                target_mean + (score - mean) / std_dev * target_std_dev
+            }
+        };
+
+        let capped = match max {
+            Some(m) => curved.clamp(0.0, m),
+            None => curved.max(0.0),
+        };
+        // Dos decimales, como en la hoja
+        Ok((capped * 100.0).round() / 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+
+    fn sample_table() -> AcademicTable {
+        fixtures::table(&["Prueba 1", "Examen"], &[
+            ("A1", "IMS", &["9/10", "16/40"]),
+            ("A2", "IMS", &["8/10", "36/40"]),
+            ("A3", "IMS", &["7/10", "4/40"]),
+            ("A4", "IMS", &["6/10", "RM"]),
+        ])
+    }
+
+    fn obtained(table: &AcademicTable, row: usize, column: usize) -> f32 {
+        extract_raw_score(&table.records[row].grades[column]).unwrap()
+    }
+
+    #[test]
+    fn test_curves() {
+        let table = sample_table();
+
+        let (curved, changes) = table.apply_curve(1, &Curve::SquareRoot).unwrap();
+        assert!((obtained(&curved, 0, 1) - 25.3).abs() < 0.01);
+        assert!((obtained(&curved, 2, 1) - 12.65).abs() < 0.01);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(curved.records[3].grades[1], GradeValue::Withdrawn);
+        // La tabla original queda intacta
+        assert_eq!(table.records[0].grades[1], GradeValue::Fraction { obtained: 16.0, total: 40.0 });
+
+        let (curved, _) = table.apply_curve(1, &Curve::AddConstant { points: 5.0 }).unwrap();
+        assert_eq!(obtained(&curved, 1, 1), 40.0); // Tope en el máximo
+        assert_eq!(obtained(&curved, 2, 1), 9.0);
+
+        let (curved, _) = table.apply_curve(1, &Curve::LinearToMean { target_mean: 28.0 }).unwrap();
+        assert_eq!(obtained(&curved, 0, 1), 24.0); // Factor 28 / 18.67 = 1.5
+        assert_eq!(obtained(&curved, 1, 1), 40.0);
+
+        let (curved, changes) = table.apply_curve(0, &Curve::ZScore { target_mean: 7.5, target_std_dev: 1.0 }).unwrap();
+        let mean: f32 = (0..4).map(|r| obtained(&curved, r, 0)).sum::<f32>() / 4.0;
+        assert!((mean - 7.5).abs() < 0.01);
+        assert_eq!(changes[0].old_value, GradeValue::Fraction { obtained: 9.0, total: 10.0 });
+    }
+
+    #[test]
+    fn test_preview_recomputes_status() {
+        let table = fixtures::table(&["Prueba 1", "Examen"], &[
+            ("A1", "IMS", &["45/50", "20/50"]),
+        ]);
+        let before = GradeStats::new(&table).summary();
+        let preview = table.preview_curve(1, &Curve::AddConstant { points: 10.0 }, &GradingPolicy::default()).unwrap();
+
+        assert_eq!(before.students[0].status, crate::rules::AcademicStatus::Failed);
+        assert_eq!(preview.summary.students[0].accumulated_score, Some(75.0));
+        assert_eq!(preview.summary.students[0].status, crate::rules::AcademicStatus::Approved);
+    }
+
+    #[test]
+    fn test_invalid_curves() {
+        let table = sample_table();
+        assert!(matches!(table.apply_curve(5, &Curve::SquareRoot), Err(EngineError::InvalidEvaluation(5))));
+
+        let flat = fixtures::table(&["Prueba"], &[("A1", "IMS", &["5/10"]), ("A2", "IMS", &["5/10"])]);
+        let z = Curve::ZScore { target_mean: 7.0, target_std_dev: 1.0 };
+        assert!(matches!(flat.apply_curve(0, &z), Err(EngineError::InvalidTransform(_))));
+    }
+}
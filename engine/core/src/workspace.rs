@@ -0,0 +1,337 @@
+//! Varios libros de notas (secciones o cursos) cargados a la vez, con consultas entre cursos.
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::{mean, variance};
+use crate::error::EngineError;
+use crate::merge::{merge_sections, MergeOptions, MergeReport};
+use crate::models::attendance::AttendanceSheet;
+use crate::models::domain::ClassSummary;
+use crate::models::gradebook::AcademicTable;
+use crate::models::stats::GradeStats;
+use crate::rules::{AcademicStatus, GradingPolicy};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Gradebook {
+    pub name: String,
+    /// Subject shared by the sections of the same course ("Matemática I").
+    pub subject: Option<String>,
+    pub table: AcademicTable,
+    pub policy: GradingPolicy,
+    #[serde(default)]
+    pub attendance: Option<AttendanceSheet>,
+}
+
+impl Gradebook {
+    pub fn new(name: impl Into<String>, table: AcademicTable) -> Self {
+        Self { name: name.into(), subject: None, table, policy: GradingPolicy::default(), attendance: None }
+    }
+
+    pub fn stats(&self) -> GradeStats<'_> {
+        GradeStats::with_attendance(&self.table, &self.policy, self.attendance.as_ref())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Workspace {
+    gradebooks: Vec<Gradebook>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct CourseStanding {
+    pub gradebook: String,
+    pub subject: Option<String>,
+    pub accumulated_score: Option<f32>,
+    pub status: AcademicStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct StudentCourseLoad {
+    pub carnet: String,
+    pub name: String,
+    pub courses: Vec<CourseStanding>,
+    pub failed_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct SectionSummary {
+    pub gradebook: String,
+    pub class: ClassSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify), tsify(into_wasm_abi))]
+pub struct SubjectSummary {
+    pub subject: String,
+    pub sections: Vec<SectionSummary>,
+    pub student_count: usize,
+    pub overall_average: Option<f32>,
+    pub overall_std_dev: Option<f32>,
+    pub approved_count: usize,
+    pub failed_count: usize,
+    /// Approved over every student across the sections, like `TermSnapshot::pass_rate`.
+    pub pass_rate: Option<f32>,
+}
+
+impl Workspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, gradebook: Gradebook) -> Result<(), EngineError> {
+        if self.get(&gradebook.name).is_some() {
+            return Err(EngineError::DuplicateGradebook(gradebook.name));
+        }
+        self.gradebooks.push(gradebook);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<Gradebook, EngineError> {
+        let idx = self.gradebooks.iter().position(|g| g.name == name)
+            .ok_or_else(|| EngineError::UnknownGradebook(name.to_string()))?;
+        Ok(self.gradebooks.remove(idx))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Gradebook> {
+        self.gradebooks.iter().find(|g| g.name == name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Gradebook> {
+        self.gradebooks.iter_mut().find(|g| g.name == name)
+    }
+
+    pub fn gradebooks(&self) -> &[Gradebook] {
+        &self.gradebooks
+    }
+
+    /// Une varias secciones en un libro `merged_name`, con el reglamento y la materia de la
+    /// primera, y lo pone en lugar de ellas para no contar dos veces a sus estudiantes.
+    pub fn merge_gradebooks(&mut self, names: &[String], merged_name: &str, options: &MergeOptions) -> Result<MergeReport, EngineError> {
+        if self.get(merged_name).is_some() && !names.iter().any(|n| n == merged_name) {
+            return Err(EngineError::DuplicateGradebook(merged_name.to_string()));
+        }
+        let sections: Vec<&Gradebook> = names.iter()
+            .map(|name| self.get(name).ok_or_else(|| EngineError::UnknownGradebook(name.clone())))
+            .collect::<Result<_, _>>()?;
+        let first = sections.first().ok_or(EngineError::EmptyInput)?;
+
+        let tables: Vec<(String, AcademicTable)> = sections.iter()
+            .map(|g| (g.name.clone(), g.table.clone()))
+            .collect();
+        let report = merge_sections(&tables, options)?;
+
+        let sheets: Vec<&AttendanceSheet> = sections.iter().filter_map(|g| g.attendance.as_ref()).collect();
+        let merged = Gradebook {
+            name: merged_name.to_string(),
+            subject: first.subject.clone(),
+            table: report.table.clone(),
+            policy: first.policy.clone(),
+            attendance: (!sheets.is_empty()).then(|| AttendanceSheet::merge(&sheets)),
+        };
+
+        // El libro unido ocupa el lugar de la primera sección
+        let position = self.gradebooks.iter().position(|g| g.name == first.name).expect("section exists");
+        let position = position - self.gradebooks[..position].iter().filter(|g| names.contains(&g.name)).count();
+        self.gradebooks.retain(|g| !names.contains(&g.name));
+        self.gradebooks.insert(position, merged);
+        Ok(report)
+    }
+
+    /// Cursos de cada estudiante, emparejados por carnet, en orden de aparición.
+    pub fn course_loads(&self) -> Vec<StudentCourseLoad> {
+        let mut loads: Vec<StudentCourseLoad> = Vec::new();
+
+        for gradebook in &self.gradebooks {
+            let students = gradebook.stats().student_summaries();
+            for (record, student) in gradebook.table.records.iter().zip(students) {
+                let carnet = record.carnet.trim();
+                let standing = CourseStanding {
+                    gradebook: gradebook.name.clone(),
+                    subject: gradebook.subject.clone(),
+                    accumulated_score: student.accumulated_score,
+                    status: student.status,
+                };
+
+                let load = match loads.iter_mut().position(|l| l.carnet == carnet) {
+                    Some(idx) => &mut loads[idx],
+                    None => {
+                        loads.push(StudentCourseLoad {
+                            carnet: carnet.to_string(),
+                            name: record.name.clone(),
+                            courses: Vec::new(),
+                            failed_count: 0,
+                        });
+                        loads.last_mut().expect("just pushed")
+                    }
+                };
+                if standing.status == AcademicStatus::Failed {
+                    load.failed_count += 1;
+                }
+                load.courses.push(standing);
+            }
+        }
+
+        loads
+    }
+
+    /// Estudiantes reprobados en `min_courses` cursos o más, los más afectados primero.
+    pub fn students_failing(&self, min_courses: usize) -> Vec<StudentCourseLoad> {
+        let mut failing: Vec<StudentCourseLoad> = self.course_loads().into_iter()
+            .filter(|l| l.failed_count >= min_courses)
+            .collect();
+        failing.sort_by_key(|l| std::cmp::Reverse(l.failed_count));
+        failing
+    }
+
+    /// Estadísticas combinadas de todas las secciones con el mismo `subject`.
+    pub fn subject_summary(&self, subject: &str) -> Option<SubjectSummary> {
+        let sections: Vec<&Gradebook> = self.gradebooks.iter()
+            .filter(|g| g.subject.as_deref().is_some_and(|s| s.trim().eq_ignore_ascii_case(subject.trim())))
+            .collect();
+        if sections.is_empty() {
+            return None;
+        }
+
+        let mut scores = Vec::new();
+        let mut approved_count = 0;
+        let mut failed_count = 0;
+        let mut student_count = 0;
+        let mut summaries = Vec::new();
+
+        for gradebook in &sections {
+            let stats = gradebook.stats();
+            for student in stats.student_summaries() {
+                student_count += 1;
+                match student.status {
+                    AcademicStatus::Approved => approved_count += 1,
+                    AcademicStatus::Failed => failed_count += 1,
+                    _ => {}
+                }
+                if let Some(score) = student.accumulated_score {
+                    scores.push(score);
+                }
+            }
+            summaries.push(SectionSummary { gradebook: gradebook.name.clone(), class: stats.class_summary() });
+        }
+
+        let overall_average = mean(&scores);
+        let overall_std_dev = variance(&scores).map(f32::sqrt);
+
+        Some(SubjectSummary {
+            subject: subject.to_string(),
+            sections: summaries,
+            student_count,
+            overall_average,
+            overall_std_dev,
+            approved_count,
+            failed_count,
+            pass_rate: (student_count > 0).then(|| approved_count as f32 / student_count as f32),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::gradebook::fixtures;
+    use crate::parser::parse_csv;
+
+    fn workspace() -> Workspace {
+        let mut workspace = Workspace::new();
+        let mut math_a = Gradebook::new("Matemática A", fixtures::table(&["Parcial", "Final"], &[
+            ("A1", "IMS", &["45/50", "40/50"]),
+            ("A2", "IMS", &["10/50", "20/50"]),
+        ]));
+        math_a.subject = Some("Matemática".to_string());
+        let mut math_b = Gradebook::new("Matemática B", fixtures::table(&["Parcial", "Final"], &[
+            ("A3", "ICE", &["35/50", "40/50"]),
+        ]));
+        math_b.subject = Some("Matemática".to_string());
+        let physics = Gradebook::new("Física", fixtures::table(&["Parcial", "Final"], &[
+            ("A2", "IMS", &["5/50", "30/50"]),
+            ("A1", "IMS", &["20/50", "10/50"]),
+        ]));
+
+        workspace.add(math_a).unwrap();
+        workspace.add(math_b).unwrap();
+        workspace.add(physics).unwrap();
+        workspace
+    }
+
+    #[test]
+    fn test_cross_course_queries() {
+        let workspace = workspace();
+
+        let loads = workspace.course_loads();
+        assert_eq!(loads.len(), 3);
+        assert_eq!(loads[0].courses.len(), 2);
+        assert_eq!(loads[0].failed_count, 1);
+
+        let failing = workspace.students_failing(2);
+        assert_eq!(failing.len(), 1);
+        assert_eq!(failing[0].carnet, "A2");
+
+        let math = workspace.subject_summary("matemática").unwrap();
+        assert_eq!(math.sections.len(), 2);
+        assert_eq!(math.student_count, 3);
+        assert_eq!((math.approved_count, math.failed_count), (2, 1));
+        assert!((math.pass_rate.unwrap() - 2.0 / 3.0).abs() < 1e-4);
+        assert!((math.overall_average.unwrap() - 63.333).abs() < 1e-2);
+        assert!(workspace.subject_summary("Química").is_none());
+    }
+
+    #[test]
+    fn test_pass_rate_counts_students_without_score() {
+        let mut workspace = workspace();
+        let mut math_c = Gradebook::new("Matemática C", fixtures::table(&["Parcial", "Final"], &[
+            ("A4", "ICE", &["", ""]),
+        ]));
+        math_c.subject = Some("Matemática".to_string());
+        workspace.add(math_c).unwrap();
+
+        let math = workspace.subject_summary("Matemática").unwrap();
+        assert_eq!(math.student_count, 4);
+        // Mismo denominador que el historial y los lotes
+        assert!((math.pass_rate.unwrap() - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_duplicate_names() {
+        let mut workspace = workspace();
+        let duplicate = Gradebook::new("Física", fixtures::table(&["P"], &[]));
+        assert!(matches!(workspace.add(duplicate), Err(EngineError::DuplicateGradebook(_))));
+        assert!(workspace.remove("Física").is_ok());
+        assert!(matches!(workspace.remove("Física"), Err(EngineError::UnknownGradebook(_))));
+    }
+
+    #[test]
+    fn test_merge_gradebooks() {
+        let mut workspace = workspace();
+        let sheet = |csv: &str| AttendanceSheet::try_from(parse_csv(csv.as_bytes()).unwrap()).unwrap();
+        workspace.get_mut("Matemática A").unwrap().attendance = Some(sheet("Carnet,Lunes,Martes\nA1,P,P\nA2,A,A\n"));
+        workspace.get_mut("Matemática B").unwrap().attendance = Some(sheet("Carnet,Martes,Jueves\nA3,P,A\n"));
+
+        let names = vec!["Matemática A".to_string(), "Matemática B".to_string()];
+        let report = workspace.merge_gradebooks(&names, "Matemática", &MergeOptions::default()).unwrap();
+
+        assert_eq!(report.table.records.len(), 3);
+        let merged = workspace.get("Matemática").unwrap();
+        assert_eq!(merged.subject.as_deref(), Some("Matemática"));
+        assert_eq!(merged.table.records[2].section.as_deref(), Some("Matemática B"));
+        assert!(workspace.get("Matemática A").is_none() && workspace.get("Matemática B").is_none());
+
+        let attendance = merged.attendance.as_ref().unwrap();
+        assert_eq!(attendance.sessions, vec!["Lunes", "Martes", "Jueves"]);
+        assert_eq!(merged.stats().student_summaries()[2].attendance_percent, Some(50.0));
+
+        // Cada estudiante sigue contando una sola vez por curso
+        let loads = workspace.course_loads();
+        assert_eq!(loads.len(), 3);
+        assert_eq!(loads.iter().map(|l| l.courses.len()).collect::<Vec<_>>(), vec![2, 2, 1]);
+        assert_eq!(loads[0].courses[0].gradebook, "Matemática");
+        assert_eq!(workspace.subject_summary("Matemática").unwrap().student_count, 3);
+    }
+}
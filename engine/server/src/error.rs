@@ -0,0 +1,39 @@
+use axum::Json;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use pna_core::error::EngineError;
+use serde_json::json;
+
+#[derive(Debug)]
+pub enum ApiError {
+    UnknownSession(String),
+    NotFound(String),
+    /// The upload or the request body could not be processed.
+    Engine(EngineError),
+    BadRequest(String),
+    /// The store is at its session limit.
+    TooManySessions(usize),
+    Internal(String),
+}
+
+impl From<EngineError> for ApiError {
+    fn from(e: EngineError) -> Self {
+        ApiError::Engine(e)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::UnknownSession(id) => (StatusCode::NOT_FOUND, format!("unknown session {}", id)),
+            ApiError::NotFound(message) => (StatusCode::NOT_FOUND, message),
+            ApiError::Engine(e) => (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()),
+            ApiError::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            ApiError::TooManySessions(max) => {
+                (StatusCode::SERVICE_UNAVAILABLE, format!("too many open sessions (at most {}); delete one and retry", max))
+            }
+            ApiError::Internal(message) => (StatusCode::INTERNAL_SERVER_ERROR, message),
+        };
+        (status, Json(json!({ "error": message }))).into_response()
+    }
+}
@@ -0,0 +1,41 @@
+//! `pna-server`: el motor de notas como API REST local, para herramientas internas que no
+//! quieren cargar el WASM.
+
+mod error;
+mod routes;
+mod sessions;
+
+use std::env;
+use std::net::SocketAddr;
+
+use sessions::SessionStore;
+
+const DEFAULT_ADDR: &str = "127.0.0.1:8080";
+
+#[tokio::main]
+async fn main() {
+    // Dirección: primer argumento, luego PNA_SERVER_ADDR; por defecto sólo localhost
+    let addr = env::args().nth(1)
+        .or_else(|| env::var("PNA_SERVER_ADDR").ok())
+        .unwrap_or_else(|| DEFAULT_ADDR.to_string());
+    let addr: SocketAddr = match addr.parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            eprintln!("error: invalid address {}: {}", addr, e);
+            std::process::exit(2);
+        }
+    };
+
+    let listener = match tokio::net::TcpListener::bind(addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("error: cannot listen on {}: {}", addr, e);
+            std::process::exit(1);
+        }
+    };
+    eprintln!("pna-server listening on http://{}", addr);
+    if let Err(e) = axum::serve(listener, routes::router(SessionStore::default())).await {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
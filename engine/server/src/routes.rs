@@ -0,0 +1,324 @@
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use pna_core::api::{
+    detect_anomalies, item_analysis, project_students, AcademicStatus, AcademicTable, Anomaly, Annotation, Curve,
+    CurvePreview, GradeStats, GradeStatsOwned, GradingPolicy, ItemAnalysis, StudentProjection,
+};
+use pna_core::export::{to_csv, to_html};
+use pna_core::models::domain::{EvaluationSummary, GradebookSummary, StudentSummary};
+use pna_core::models::gradebook::StudentRecord;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ApiError;
+use crate::sessions::{parse_upload, SessionStore};
+
+/// Workbooks with many sheets easily pass axum's 2 MB default.
+const MAX_UPLOAD_BYTES: usize = 32 * 1024 * 1024;
+
+pub fn router(store: SessionStore) -> Router {
+    Router::new()
+        .route("/sessions", post(create_session))
+        .route("/sessions/{id}", axum::routing::delete(delete_session))
+        .route("/sessions/{id}/summary", get(summary))
+        .route("/sessions/{id}/table", get(table))
+        .route("/sessions/{id}/students", get(students))
+        .route("/sessions/{id}/students/{carnet}", get(student))
+        .route("/sessions/{id}/evaluations", get(evaluations))
+        .route("/sessions/{id}/evaluations/{index}", get(evaluation))
+        .route("/sessions/{id}/policy", get(policy).put(set_policy))
+        .route("/sessions/{id}/projections", get(projections))
+        .route("/sessions/{id}/anomalies", get(anomalies))
+        .route("/sessions/{id}/export/{format}", get(export))
+        .route("/sessions/{id}/simulate/curve", post(simulate_curve))
+        .route("/sessions/{id}/simulate/policy", post(simulate_policy))
+        .layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES))
+        .with_state(store)
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+#[derive(Serialize)]
+struct Created {
+    id: String,
+    summary: GradeStatsOwned,
+}
+
+/// El cuerpo es el archivo tal cual (xlsx, xls, ods o `.pna`).
+async fn create_session(State(store): State<SessionStore>, body: Bytes) -> Result<(StatusCode, Json<Created>), ApiError> {
+    // Leer el libro y calcular el resumen bloquea; no debe frenar a las demás peticiones
+    let (state, summary) = tokio::task::spawn_blocking(move || {
+        let state = parse_upload(&body)?;
+        let summary = GradeStatsOwned::from(&state.stats());
+        Ok::<_, ApiError>((state, summary))
+    }).await.map_err(|e| ApiError::Internal(e.to_string()))??;
+    let id = store.create(state).await?;
+    Ok((StatusCode::CREATED, Json(Created { id, summary })))
+}
+
+async fn delete_session(State(store): State<SessionStore>, Path(id): Path<String>) -> Result<StatusCode, ApiError> {
+    store.remove(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn summary(State(store): State<SessionStore>, Path(id): Path<String>) -> ApiResult<GradebookSummary> {
+    store.with(&id, |state| Ok(Json(state.stats().summary()))).await
+}
+
+async fn table(State(store): State<SessionStore>, Path(id): Path<String>) -> ApiResult<AcademicTable> {
+    store.with(&id, |state| Ok(Json(state.table.clone()))).await
+}
+
+#[derive(Deserialize)]
+struct StudentFilter {
+    /// Comma separated statuses, any case: `?status=critical,failed`.
+    status: Option<String>,
+}
+
+async fn students(State(store): State<SessionStore>, Path(id): Path<String>, Query(filter): Query<StudentFilter>) -> ApiResult<Vec<StudentSummary>> {
+    let statuses = filter.status.as_deref().map(parse_statuses).transpose()?;
+    store.with(&id, |state| {
+        let rows = state.stats().student_summaries().into_iter()
+            .filter(|s| statuses.as_ref().is_none_or(|statuses| statuses.contains(&s.status)))
+            .collect();
+        Ok(Json(rows))
+    }).await
+}
+
+fn parse_statuses(text: &str) -> Result<Vec<AcademicStatus>, ApiError> {
+    const STATUSES: [AcademicStatus; 5] = [
+        AcademicStatus::Approved,
+        AcademicStatus::OnTrack,
+        AcademicStatus::Warning,
+        AcademicStatus::Critical,
+        AcademicStatus::Failed,
+    ];
+    text.split(',').map(|name| {
+        let name: String = name.trim().chars().filter(|c| c.is_alphanumeric()).collect();
+        STATUSES.iter()
+            .find(|status| format!("{:?}", status).eq_ignore_ascii_case(&name))
+            .cloned()
+            .ok_or_else(|| ApiError::BadRequest(format!("unknown status {}", name)))
+    }).collect()
+}
+
+#[derive(Serialize)]
+struct StudentDetail {
+    record: StudentRecord,
+    summary: StudentSummary,
+    projection: Option<StudentProjection>,
+    annotations: Vec<Annotation>,
+}
+
+async fn student(State(store): State<SessionStore>, Path((id, carnet)): Path<(String, String)>) -> ApiResult<StudentDetail> {
+    store.with(&id, |state| {
+        let row = state.table.records.iter()
+            .position(|r| r.carnet.trim().eq_ignore_ascii_case(carnet.trim()))
+            .ok_or_else(|| ApiError::NotFound(format!("no student with carnet {}", carnet)))?;
+        let stats = state.stats();
+        let annotations = state.annotations.iter()
+            .filter(|a| a.carnet.as_deref().is_some_and(|c| c.trim().eq_ignore_ascii_case(carnet.trim())))
+            .cloned()
+            .collect();
+        Ok(Json(StudentDetail {
+            record: state.table.records[row].clone(),
+            summary: stats.student_summaries().swap_remove(row),
+            projection: stats.student_projections().get(row).cloned(),
+            annotations,
+        }))
+    }).await
+}
+
+async fn evaluations(State(store): State<SessionStore>, Path(id): Path<String>) -> ApiResult<Vec<EvaluationSummary>> {
+    store.with(&id, |state| Ok(Json(state.stats().evaluation_summaries()))).await
+}
+
+#[derive(Serialize)]
+struct EvaluationDetail {
+    summary: EvaluationSummary,
+    item_analysis: ItemAnalysis,
+}
+
+async fn evaluation(State(store): State<SessionStore>, Path((id, index)): Path<(String, usize)>) -> ApiResult<EvaluationDetail> {
+    store.with(&id, |state| {
+        if index >= state.table.evaluations.len() {
+            return Err(ApiError::NotFound(format!("no evaluation {}", index)));
+        }
+        Ok(Json(EvaluationDetail {
+            summary: state.stats().evaluation_summaries().swap_remove(index),
+            item_analysis: item_analysis(&state.table, &state.policy).swap_remove(index),
+        }))
+    }).await
+}
+
+async fn policy(State(store): State<SessionStore>, Path(id): Path<String>) -> ApiResult<GradingPolicy> {
+    store.with(&id, |state| Ok(Json(state.policy.clone()))).await
+}
+
+/// Reemplaza el reglamento de la sesión y devuelve el resumen recalculado.
+async fn set_policy(State(store): State<SessionStore>, Path(id): Path<String>, Json(policy): Json<GradingPolicy>) -> ApiResult<GradebookSummary> {
+    store.with_mut(&id, |state| {
+        state.policy = policy;
+        Ok(Json(state.stats().summary()))
+    }).await
+}
+
+async fn projections(State(store): State<SessionStore>, Path(id): Path<String>) -> ApiResult<Vec<StudentProjection>> {
+    store.with(&id, |state| Ok(Json(project_students(&state.table, &state.policy)))).await
+}
+
+async fn anomalies(State(store): State<SessionStore>, Path(id): Path<String>) -> ApiResult<Vec<Anomaly>> {
+    store.with(&id, |state| Ok(Json(detect_anomalies(&state.table)))).await
+}
+
+/// `csv`, `html` o `pna` (la sesión completa, para volver a subirla después).
+async fn export(State(store): State<SessionStore>, Path((id, format)): Path<(String, String)>) -> Result<Response, ApiError> {
+    store.with(&id, |state| {
+        let response = match format.as_str() {
+            "csv" => ([(header::CONTENT_TYPE, "text/csv; charset=utf-8")], to_csv(state)).into_response(),
+            "html" => {
+                let title = state.description_headers.first().map(String::as_str).unwrap_or("Notas");
+                ([(header::CONTENT_TYPE, "text/html; charset=utf-8")], to_html(title, state)).into_response()
+            }
+            "pna" => (
+                [
+                    (header::CONTENT_TYPE, "application/octet-stream"),
+                    (header::CONTENT_DISPOSITION, "attachment; filename=\"session.pna\""),
+                ],
+                state.to_bytes()?,
+            ).into_response(),
+            other => return Err(ApiError::NotFound(format!("unknown export format {}", other))),
+        };
+        Ok(response)
+    }).await
+}
+
+#[derive(Deserialize)]
+struct CurveRequest {
+    /// Evaluation index, as in `/evaluations/{index}`.
+    evaluation: usize,
+    curve: Curve,
+}
+
+/// Vista previa de una curva; la sesión no cambia.
+async fn simulate_curve(State(store): State<SessionStore>, Path(id): Path<String>, Json(request): Json<CurveRequest>) -> ApiResult<CurvePreview> {
+    store.with(&id, |state| Ok(Json(state.table.preview_curve(request.evaluation, &request.curve, &state.policy)?))).await
+}
+
+/// Resumen con otro reglamento; la sesión no cambia.
+async fn simulate_policy(State(store): State<SessionStore>, Path(id): Path<String>, Json(policy): Json<GradingPolicy>) -> ApiResult<GradebookSummary> {
+    store.with(&id, |state| {
+        Ok(Json(GradeStats::with_attendance(&state.table, &policy, state.attendance.as_ref())
+            .with_makeup(&state.makeup_grades)
+            .summary()))
+    }).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use pna_core::api::EngineState;
+    use pna_core::models::gradebook::GradeValue;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    fn state() -> EngineState {
+        let record = |carnet: &str, scores: [f32; 2]| StudentRecord {
+            carnet: carnet.to_string(),
+            name: format!("Estudiante {}", carnet),
+            email: String::new(),
+            group: "IMS".to_string(),
+            section: None,
+            grades: scores.iter().map(|&obtained| GradeValue::Fraction { obtained, total: 50.0 }).collect(),
+            final_grade: GradeValue::Absent,
+        };
+        EngineState {
+            description_headers: vec!["Cálculo I".to_string()],
+            table: AcademicTable {
+                evaluations: vec!["Parcial".to_string(), "Final".to_string()],
+                records: vec![record("A1", [45.0, 40.0]), record("A2", [10.0, 5.0]), record("A3", [38.0, 36.0])],
+            },
+            policy: GradingPolicy::default(),
+            attendance: None,
+            makeup_grades: Vec::new(),
+            annotations: vec![Annotation { carnet: Some("A2".to_string()), evaluation: None, text: "Tutoría".to_string() }],
+        }
+    }
+
+    async fn call(app: &Router, method: &str, uri: &str, body: Body) -> (StatusCode, Bytes) {
+        let request = Request::builder().method(method).uri(uri).header(header::CONTENT_TYPE, "application/json").body(body).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        (status, response.into_body().collect().await.unwrap().to_bytes())
+    }
+
+    async fn json_call(app: &Router, method: &str, uri: &str, body: Body) -> (StatusCode, Value) {
+        let (status, bytes) = call(app, method, uri, body).await;
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+
+    /// Sube la sesión como `.pna`, igual que un cliente que reabre un trabajo guardado.
+    async fn upload(app: &Router) -> String {
+        let (status, created) = json_call(app, "POST", "/sessions", Body::from(state().to_bytes().unwrap())).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["summary"]["class"]["student_count"], 3);
+        created["id"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_session_endpoints() {
+        let app = router(SessionStore::default());
+        let id = upload(&app).await;
+
+        let (_, failed) = json_call(&app, "GET", &format!("/sessions/{}/students?status=failed", id), Body::empty()).await;
+        assert_eq!(failed.as_array().unwrap().len(), 1);
+        assert_eq!(failed[0]["id"], "A2");
+
+        let (_, detail) = json_call(&app, "GET", &format!("/sessions/{}/students/a2", id), Body::empty()).await;
+        assert_eq!(detail["annotations"][0]["text"], "Tutoría");
+        assert_eq!(detail["summary"]["status"], "Failed");
+
+        let (_, evaluation) = json_call(&app, "GET", &format!("/sessions/{}/evaluations/1", id), Body::empty()).await;
+        assert_eq!(evaluation["summary"]["name"], "Final");
+        let (status, _) = call(&app, "GET", &format!("/sessions/{}/evaluations/9", id), Body::empty()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, csv) = call(&app, "GET", &format!("/sessions/{}/export/csv", id), Body::empty()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(String::from_utf8(csv.to_vec()).unwrap().starts_with("Carnet,Nombre"));
+
+        let (status, _) = call(&app, "DELETE", &format!("/sessions/{}", id), Body::empty()).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, error) = json_call(&app, "GET", &format!("/sessions/{}/summary", id), Body::empty()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(error["error"].as_str().unwrap().contains("unknown session"));
+    }
+
+    #[tokio::test]
+    async fn test_simulations_do_not_change_the_session() {
+        let app = router(SessionStore::default());
+        let id = upload(&app).await;
+
+        let curve = json!({ "evaluation": 0, "curve": { "type": "AddConstant", "points": 5.0 } });
+        let (status, preview) = json_call(&app, "POST", &format!("/sessions/{}/simulate/curve", id), Body::from(curve.to_string())).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(preview["changes"].as_array().unwrap().len(), 3);
+
+        let strict = serde_json::to_string(&GradingPolicy { passing_score: 90.0, ..GradingPolicy::default() }).unwrap();
+        let (_, simulated) = json_call(&app, "POST", &format!("/sessions/{}/simulate/policy", id), Body::from(strict)).await;
+        assert_eq!(simulated["class"]["failed_count"], 3);
+
+        let (_, summary) = json_call(&app, "GET", &format!("/sessions/{}/summary", id), Body::empty()).await;
+        assert_eq!(summary["class"]["failed_count"], 1);
+        assert_eq!(summary["evaluations"][0]["highest_score"], 45.0);
+
+        let (status, _) = call(&app, "POST", "/sessions", Body::from("not a workbook")).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,137 @@
+//! Libros cargados, en memoria y por id de sesión. Nada se escribe a disco.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use pna_core::api::EngineState;
+use pna_core::error::EngineError;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+
+/// Sessions kept at once; past this, new uploads are refused until one is deleted or expires.
+pub const MAX_SESSIONS: usize = 64;
+/// A session nobody has used for this long is dropped.
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+struct Session {
+    state: EngineState,
+    last_used: Mutex<Instant>,
+}
+
+impl Session {
+    fn new(state: EngineState) -> Self {
+        Self { state, last_used: Mutex::new(Instant::now()) }
+    }
+
+    fn is_idle(&self, timeout: Duration) -> bool {
+        self.last_used.lock().map_or(true, |t| t.elapsed() >= timeout)
+    }
+
+    fn touch(&self) {
+        if let Ok(mut last_used) = self.last_used.lock() {
+            *last_used = Instant::now();
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SessionStore {
+    sessions: Arc<RwLock<HashMap<String, Session>>>,
+    max_sessions: usize,
+    idle_timeout: Duration,
+}
+
+impl Default for SessionStore {
+    fn default() -> Self {
+        Self::new(MAX_SESSIONS, IDLE_TIMEOUT)
+    }
+}
+
+impl SessionStore {
+    pub fn new(max_sessions: usize, idle_timeout: Duration) -> Self {
+        Self { sessions: Arc::default(), max_sessions, idle_timeout }
+    }
+
+    /// Antes de rechazar por cupo se descartan las sesiones vencidas.
+    pub async fn create(&self, state: EngineState) -> Result<String, ApiError> {
+        let mut sessions = self.sessions.write().await;
+        sessions.retain(|_, session| !session.is_idle(self.idle_timeout));
+        if sessions.len() >= self.max_sessions {
+            return Err(ApiError::TooManySessions(self.max_sessions));
+        }
+        let id = Uuid::new_v4().to_string();
+        sessions.insert(id.clone(), Session::new(state));
+        Ok(id)
+    }
+
+    pub async fn remove(&self, id: &str) -> Result<(), ApiError> {
+        self.sessions.write().await.remove(id).map(|_| ()).ok_or_else(|| ApiError::UnknownSession(id.to_string()))
+    }
+
+    pub async fn with<T>(&self, id: &str, f: impl FnOnce(&EngineState) -> Result<T, ApiError>) -> Result<T, ApiError> {
+        let sessions = self.sessions.read().await;
+        let session = self.live(sessions.get(id), id)?;
+        session.touch();
+        f(&session.state)
+    }
+
+    pub async fn with_mut<T>(&self, id: &str, f: impl FnOnce(&mut EngineState) -> Result<T, ApiError>) -> Result<T, ApiError> {
+        let mut sessions = self.sessions.write().await;
+        self.live(sessions.get(id), id)?;
+        let session = sessions.get_mut(id).expect("checked above");
+        session.touch();
+        f(&mut session.state)
+    }
+
+    /// Una sesión vencida cuenta como inexistente aunque todavía no se haya descartado.
+    fn live<'a>(&self, session: Option<&'a Session>, id: &str) -> Result<&'a Session, ApiError> {
+        session.filter(|s| !s.is_idle(self.idle_timeout)).ok_or_else(|| ApiError::UnknownSession(id.to_string()))
+    }
+}
+
+/// Un libro de Excel/ODS, o una sesión `.pna` exportada antes.
+pub fn parse_upload(data: &[u8]) -> Result<EngineState, EngineError> {
+    if data.starts_with(b"PNA") {
+        return EngineState::from_bytes(data);
+    }
+    EngineState::from_workbook(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pna_core::api::{AcademicTable, GradingPolicy};
+
+    fn state() -> EngineState {
+        EngineState {
+            description_headers: Vec::new(),
+            table: AcademicTable { evaluations: Vec::new(), records: Vec::new() },
+            policy: GradingPolicy::default(),
+            attendance: None,
+            makeup_grades: Vec::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_limit() {
+        let store = SessionStore::new(1, IDLE_TIMEOUT);
+        let id = store.create(state()).await.unwrap();
+        assert!(matches!(store.create(state()).await, Err(ApiError::TooManySessions(1))));
+
+        store.remove(&id).await.unwrap();
+        assert!(store.create(state()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_idle_sessions_expire() {
+        let store = SessionStore::new(1, Duration::ZERO);
+        let id = store.create(state()).await.unwrap();
+        assert!(matches!(store.with(&id, |_| Ok(())).await, Err(ApiError::UnknownSession(_))));
+        // La vencida deja su lugar libre
+        assert!(store.create(state()).await.is_ok());
+    }
+}
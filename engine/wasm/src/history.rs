@@ -0,0 +1,51 @@
+use pna_core::api::{CohortComparison, HistoryStore, TermSnapshot, Trend};
+use wasm_bindgen::prelude::*;
+
+use crate::types::{to_js, EvaluationDriftList, TermSnapshotList};
+
+/// Historial de snapshots por ciclo; el frontend lo guarda serializado con `get_snapshots`.
+#[wasm_bindgen(js_name = History)]
+pub struct GradeHistory {
+    store: HistoryStore,
+}
+
+#[wasm_bindgen(js_class = History)]
+impl GradeHistory {
+    #[wasm_bindgen(constructor)]
+    pub fn new(snapshots: Option<TermSnapshotList>) -> Result<GradeHistory, JsValue> {
+        let mut store = HistoryStore::new();
+        if let Some(snapshots) = snapshots {
+            let snapshots: Vec<TermSnapshot> = serde_wasm_bindgen::from_value(snapshots.into())
+                .map_err(|e| JsValue::from_str(&e.to_string()))?;
+            for snapshot in snapshots {
+                store.record(snapshot);
+            }
+        }
+        Ok(Self { store })
+    }
+
+    pub fn record(&mut self, snapshot: TermSnapshot) {
+        self.store.record(snapshot);
+    }
+
+    pub fn get_snapshots(&self) -> Result<TermSnapshotList, JsValue> {
+        to_js(self.store.snapshots())
+    }
+
+    pub fn get_pass_rate_trend(&self, course: &str) -> Trend {
+        self.store.pass_rate_trend(course)
+    }
+
+    pub fn get_inflation_trend(&self, course: &str) -> Trend {
+        self.store.inflation_trend(course)
+    }
+
+    pub fn get_difficulty_drift(&self, course: &str) -> Result<EvaluationDriftList, JsValue> {
+        to_js(&self.store.difficulty_drift(course))
+    }
+
+    /// Compara un snapshot del ciclo en curso con los ciclos anteriores en el mismo punto.
+    pub fn compare_current(&self, snapshot: TermSnapshot) -> Option<CohortComparison> {
+        self.store.compare_current(&snapshot)
+    }
+}
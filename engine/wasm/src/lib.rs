@@ -1,2 +1,4 @@
-
-pub mod state;
\ No newline at end of file
+pub mod state;
+pub mod workspace;
+pub mod history;
+pub mod types;
\ No newline at end of file
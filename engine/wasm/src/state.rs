@@ -1,40 +1,217 @@
-use pna_core::{api::{GradeStatsOwned, AcademicTable, parse_excel}};
+use pna_core::api::{
+    cohort_report, correlation_matrix, detect_anomalies, diff_with_stats, group_report, item_analysis, makeup_summary,
+    parse_csv, parse_sheet, project_students, reliability, similarity_report, AcademicTable, Annotation, AttendanceSheet,
+    CohortOptions, CohortReport, CorrelationMatrix, Curve, CurvePreview, EngineState, GradeStats, GradeStatsOwned,
+    GradebookDiff, GradingPolicy, GroupReport, MakeupGrade, MakeupSummary, ReliabilityReport, SimilarityOptions,
+    SimilarityReport, TermSnapshot,
+};
 use wasm_bindgen::prelude::*;
 
+use crate::types::{to_js, AnnotationList, AnomalyList, AttendanceSummaryList, CellChangeList, ItemAnalysisList, StudentProjectionList};
+
 #[wasm_bindgen]
 pub struct GradeEngine {
     description_headers: Vec<String>,
     table: AcademicTable,
+    policy: GradingPolicy,
     stats: GradeStatsOwned,
+    attendance: Option<AttendanceSheet>,
+    makeup_grades: Vec<MakeupGrade>,
+    annotations: Vec<Annotation>,
 }
 
 #[wasm_bindgen]
 impl GradeEngine {
     #[wasm_bindgen(constructor)]
     pub fn new(csv_data: &[u8]) -> Result<GradeEngine, JsValue> {
-        let (description_headers, raw) = parse_excel(csv_data)
+        let state = EngineState::from_workbook(csv_data)
             .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(Self::from_engine_state(state))
+    }
 
-        let table = AcademicTable::try_from(raw)
+    /// Restaura una sesión guardada con `export_state`.
+    pub fn from_state(bytes: &[u8]) -> Result<GradeEngine, JsValue> {
+        let state = EngineState::from_bytes(bytes)
             .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(Self::from_engine_state(state))
+    }
 
-        let stats = GradeStatsOwned::from(&table);
+    /// Estado completo (tabla, encabezados, reglamento, asistencia, reposiciones y notas) en bytes.
+    pub fn export_state(&self) -> Result<Vec<u8>, JsValue> {
+        let state = EngineState {
+            description_headers: self.description_headers.clone(),
+            table: self.table.clone(),
+            policy: self.policy.clone(),
+            attendance: self.attendance.clone(),
+            makeup_grades: self.makeup_grades.clone(),
+            annotations: self.annotations.clone(),
+        };
+        state.to_bytes()
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
 
-        Ok(Self { description_headers, table, stats })
+    pub fn add_annotation(&mut self, annotation: Annotation) {
+        self.annotations.push(annotation);
     }
 
-    pub fn get_summary(&self) -> Result<JsValue, JsValue> {
-        serde_wasm_bindgen::to_value(&self.stats)
-            .map_err(|e| JsValue::from_str(&e.to_string()))
+    pub fn remove_annotation(&mut self, index: usize) -> Result<(), JsValue> {
+        if index >= self.annotations.len() {
+            return Err(JsValue::from_str(&format!("Annotation {} does not exist", index)));
+        }
+        self.annotations.remove(index);
+        Ok(())
     }
 
-    pub fn get_table(&self) -> Result<JsValue, JsValue> {
-        serde_wasm_bindgen::to_value(&self.table)
-            .map_err(|e| JsValue::from_str(&e.to_string()))
+    pub fn get_annotations(&self) -> Result<AnnotationList, JsValue> {
+        to_js(&self.annotations)
+    }
+
+    /// Reemplaza el libro por una versión nueva del mismo archivo y devuelve qué cambió.
+    pub fn reload(&mut self, data: &[u8]) -> Result<GradebookDiff, JsValue> {
+        let EngineState { description_headers, table, .. } = EngineState::from_workbook(data)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+        let new_stats = GradeStats::with_attendance(&table, &self.policy, self.attendance.as_ref())
+            .with_makeup(&self.makeup_grades);
+        let changes = diff_with_stats(&self.live_stats().with_makeup(&self.makeup_grades), &new_stats);
+        self.description_headers = description_headers;
+        self.table = table;
+        self.refresh_stats();
+        Ok(changes)
+    }
+
+    /// Carga la asistencia desde su propio archivo (Excel u hoja de cálculo, o CSV).
+    pub fn load_attendance(&mut self, data: &[u8]) -> Result<(), JsValue> {
+        let raw = parse_sheet(data, None)
+            .or_else(|_| parse_csv(data))
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let sheet = AttendanceSheet::try_from(raw)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+        self.attendance = Some(sheet);
+        self.refresh_stats();
+        Ok(())
+    }
+
+    pub fn get_attendance(&self) -> Result<AttendanceSummaryList, JsValue> {
+        let summaries = self.attendance.as_ref().map(|a| a.summaries(&self.policy.attendance));
+        to_js(&summaries)
+    }
+
+    pub fn get_policy(&self) -> GradingPolicy {
+        self.policy.clone()
+    }
+
+    /// Reemplaza el reglamento y recalcula acumulados, puntos perdidos y estados.
+    pub fn set_policy(&mut self, policy: GradingPolicy) {
+        self.policy = policy;
+        self.refresh_stats();
+    }
+
+    pub fn get_summary(&self) -> GradeStatsOwned {
+        self.stats.clone()
+    }
+
+    pub fn get_table(&self) -> AcademicTable {
+        self.table.clone()
+    }
+
+    pub fn get_description_headers(&self) -> Vec<String> {
+        self.description_headers.clone()
+    }
+
+    pub fn get_item_analysis(&self) -> Result<ItemAnalysisList, JsValue> {
+        to_js(&item_analysis(&self.table, &self.policy))
+    }
+
+    pub fn get_correlation_matrix(&self) -> CorrelationMatrix {
+        correlation_matrix(&self.table)
+    }
+
+    pub fn get_reliability(&self) -> ReliabilityReport {
+        reliability(&self.table)
+    }
+
+    pub fn get_group_report(&self) -> GroupReport {
+        group_report(&self.table, &self.policy)
+    }
+
+    pub fn get_projections(&self) -> Result<StudentProjectionList, JsValue> {
+        to_js(&project_students(&self.table, &self.policy))
+    }
+
+    pub fn get_anomalies(&self) -> Result<AnomalyList, JsValue> {
+        to_js(&detect_anomalies(&self.table))
+    }
+
+    /// Análisis opcional: pares de estudiantes con patrones de notas inusualmente parecidos.
+    /// Sin `options` usa los valores por defecto; bajar `permutations` lo hace más rápido.
+    pub fn get_similarity_report(&self, options: Option<SimilarityOptions>) -> SimilarityReport {
+        similarity_report(&self.table, &options.unwrap_or_default())
+    }
+
+    /// Resumen por cohorte de ingreso según el carnet.
+    pub fn get_cohort_report(&self, options: CohortOptions) -> CohortReport {
+        cohort_report(&self.table, &self.policy, &options)
     }
 
-    pub fn get_description_headers(&self) -> Result<JsValue, JsValue> {
-        serde_wasm_bindgen::to_value(&self.description_headers)
+    /// Vista previa de una curva: tabla curvada, celdas cambiadas y resumen recalculado.
+    pub fn preview_curve(&self, eval_idx: usize, curve: Curve) -> Result<CurvePreview, JsValue> {
+        self.table.preview_curve(eval_idx, &curve, &self.policy)
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
+
+    /// Aplica la curva sobre la tabla cargada y devuelve las celdas cambiadas.
+    pub fn apply_curve(&mut self, eval_idx: usize, curve: Curve) -> Result<CellChangeList, JsValue> {
+        let (table, changes) = self.table.apply_curve(eval_idx, &curve)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+        self.table = table;
+        self.refresh_stats();
+        to_js(&changes)
+    }
+
+    /// Snapshot del estado actual para guardarlo en el historial del curso.
+    pub fn snapshot(&self, course: String, term: String) -> TermSnapshot {
+        let summary = self.live_stats().with_makeup(&self.makeup_grades).summary();
+        TermSnapshot::new(course, term, summary, &self.policy)
+    }
+
+    /// Registra (o corrige) la nota de reposición de un estudiante.
+    pub fn set_makeup_grade(&mut self, carnet: String, score: f32) {
+        match self.makeup_grades.iter_mut().find(|g| g.carnet == carnet) {
+            Some(grade) => grade.score = score,
+            None => self.makeup_grades.push(MakeupGrade { carnet, score }),
+        }
+        self.refresh_stats();
+    }
+
+    /// Elegibles para reposición y nota final de quienes ya la presentaron.
+    pub fn get_makeup_summary(&self) -> MakeupSummary {
+        makeup_summary(&self.live_stats(), &self.makeup_grades)
+    }
+}
+
+impl GradeEngine {
+    fn from_engine_state(state: EngineState) -> Self {
+        let stats = GradeStatsOwned::from(&state.stats());
+        Self {
+            description_headers: state.description_headers,
+            table: state.table,
+            policy: state.policy,
+            stats,
+            attendance: state.attendance,
+            makeup_grades: state.makeup_grades,
+            annotations: state.annotations,
+        }
+    }
+
+    /// Estadísticas con asistencia, sin aplicar todavía las notas de reposición.
+    fn live_stats(&self) -> GradeStats<'_> {
+        GradeStats::with_attendance(&self.table, &self.policy, self.attendance.as_ref())
+    }
+
+    fn refresh_stats(&mut self) {
+        self.stats = GradeStatsOwned::from(&self.live_stats().with_makeup(&self.makeup_grades));
+    }
 }
@@ -0,0 +1,33 @@
+//! Tipos TypeScript de los resultados que son arreglos: tsify tipa structs y enums, no `Vec`.
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Annotation[]")]
+    pub type AnnotationList;
+    #[wasm_bindgen(typescript_type = "AttendanceSummary[] | undefined")]
+    pub type AttendanceSummaryList;
+    #[wasm_bindgen(typescript_type = "ItemAnalysis[]")]
+    pub type ItemAnalysisList;
+    #[wasm_bindgen(typescript_type = "StudentProjection[]")]
+    pub type StudentProjectionList;
+    #[wasm_bindgen(typescript_type = "Anomaly[]")]
+    pub type AnomalyList;
+    #[wasm_bindgen(typescript_type = "CellChange[]")]
+    pub type CellChangeList;
+    #[wasm_bindgen(typescript_type = "TermSnapshot[]")]
+    pub type TermSnapshotList;
+    #[wasm_bindgen(typescript_type = "EvaluationDrift[]")]
+    pub type EvaluationDriftList;
+    #[wasm_bindgen(typescript_type = "StudentCourseLoad[]")]
+    pub type StudentCourseLoadList;
+}
+
+/// Serializa `value` y lo entrega con el tipo TypeScript `J`.
+pub fn to_js<T: Serialize + ?Sized, J: JsCast>(value: &T) -> Result<J, JsValue> {
+    serde_wasm_bindgen::to_value(value)
+        .map(JsCast::unchecked_into)
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
@@ -0,0 +1,78 @@
+use pna_core::api::{EngineState, Gradebook, GradeStatsOwned, GradingPolicy, MergeOptions, MergeReport, SubjectSummary, Workspace};
+use wasm_bindgen::prelude::*;
+
+use crate::types::{to_js, StudentCourseLoadList};
+
+/// Varios libros de notas a la vez (una sección o curso por libro).
+#[wasm_bindgen(js_name = Workspace)]
+pub struct GradeWorkspace {
+    workspace: Workspace,
+}
+
+#[wasm_bindgen(js_class = Workspace)]
+impl GradeWorkspace {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> GradeWorkspace {
+        Self { workspace: Workspace::new() }
+    }
+
+    /// Agrega un libro; `subject` agrupa las secciones del mismo curso.
+    pub fn add_gradebook(&mut self, name: String, data: &[u8], subject: Option<String>) -> Result<(), JsValue> {
+        let state = EngineState::from_workbook(data)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+        let mut gradebook = Gradebook::new(name, state.table);
+        gradebook.subject = subject;
+        gradebook.attendance = state.attendance;
+
+        self.workspace.add(gradebook)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    pub fn remove_gradebook(&mut self, name: &str) -> Result<(), JsValue> {
+        self.workspace.remove(name)
+            .map(|_| ())
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    pub fn get_gradebook_names(&self) -> Vec<String> {
+        self.workspace.gradebooks().iter().map(|g| g.name.clone()).collect()
+    }
+
+    pub fn set_policy(&mut self, name: &str, policy: GradingPolicy) -> Result<(), JsValue> {
+        let gradebook = self.workspace.get_mut(name)
+            .ok_or_else(|| JsValue::from_str(&format!("No gradebook named '{}'", name)))?;
+        gradebook.policy = policy;
+        Ok(())
+    }
+
+    pub fn get_summary(&self, name: &str) -> Result<GradeStatsOwned, JsValue> {
+        let gradebook = self.workspace.get(name)
+            .ok_or_else(|| JsValue::from_str(&format!("No gradebook named '{}'", name)))?;
+        Ok(GradeStatsOwned::from(&gradebook.stats()))
+    }
+
+    /// Une secciones en un libro que las reemplaza; devuelve la lista de revisión y los carnets repetidos.
+    pub fn merge_gradebooks(&mut self, names: Vec<String>, merged_name: &str, options: Option<MergeOptions>) -> Result<MergeReport, JsValue> {
+        self.workspace.merge_gradebooks(&names, merged_name, &options.unwrap_or_default())
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    pub fn get_course_loads(&self) -> Result<StudentCourseLoadList, JsValue> {
+        to_js(&self.workspace.course_loads())
+    }
+
+    pub fn get_students_failing(&self, min_courses: usize) -> Result<StudentCourseLoadList, JsValue> {
+        to_js(&self.workspace.students_failing(min_courses))
+    }
+
+    pub fn get_subject_summary(&self, subject: &str) -> Option<SubjectSummary> {
+        self.workspace.subject_summary(subject)
+    }
+}
+
+impl Default for GradeWorkspace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
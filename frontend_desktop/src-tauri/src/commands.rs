@@ -0,0 +1,184 @@
+use std::fs;
+
+use std::sync::Mutex;
+
+use pna_core::api::{
+    AcademicTable, EngineState, EvaluationDrift, GradeStatsOwned, GradingPolicy, TermSnapshot, Trend,
+};
+use pna_core::export::to_csv;
+use serde::Serialize;
+use tauri::{AppHandle, State};
+
+use crate::library::{CourseKey, Library, LibraryEntry, StudentTerm};
+use crate::state::{AppState, OpenGradebook};
+use crate::watcher;
+
+/// Lee y procesa el archivo fuera del hilo principal.
+#[tauri::command(async)]
+pub fn open_gradebook(path: String, state: State<'_, AppState>) -> Result<GradeStatsOwned, String> {
+    // Ruta canónica, para reconocerla en los eventos del watcher
+    let path = fs::canonicalize(&path).map_err(|e| format!("{}: {}", path, e))?;
+    let data = fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let gradebook = OpenGradebook::from_bytes(&data, Some(path))?;
+    let stats = gradebook.stats();
+    *state.lock()? = Some(gradebook);
+    Ok(stats)
+}
+
+/// Para archivos soltados en la ventana, que llegan como bytes y no como ruta.
+#[tauri::command(async)]
+pub fn load_gradebook(bytes: Vec<u8>, state: State<'_, AppState>) -> Result<GradeStatsOwned, String> {
+    let gradebook = OpenGradebook::from_bytes(&bytes, None)?;
+    let stats = gradebook.stats();
+    *state.lock()? = Some(gradebook);
+    Ok(stats)
+}
+
+#[tauri::command]
+pub fn get_summary(state: State<'_, AppState>) -> Result<GradeStatsOwned, String> {
+    state.with(|g| Ok(g.stats()))
+}
+
+#[tauri::command]
+pub fn get_table(state: State<'_, AppState>) -> Result<AcademicTable, String> {
+    state.with(|g| Ok(g.state.table.clone()))
+}
+
+#[tauri::command]
+pub fn get_description_headers(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    state.with(|g| Ok(g.state.description_headers.clone()))
+}
+
+#[tauri::command]
+pub fn get_policy(state: State<'_, AppState>) -> Result<GradingPolicy, String> {
+    state.with(|g| Ok(g.state.policy.clone()))
+}
+
+/// Reemplaza el reglamento y devuelve el resumen recalculado.
+#[tauri::command]
+pub fn set_policy(policy: GradingPolicy, state: State<'_, AppState>) -> Result<GradeStatsOwned, String> {
+    state.with(|g| {
+        g.state.policy = policy;
+        Ok(g.stats())
+    })
+}
+
+#[tauri::command(async)]
+pub fn export_csv(path: String, state: State<'_, AppState>) -> Result<(), String> {
+    let csv = state.with(|g| Ok(to_csv(&g.state)))?;
+    fs::write(&path, csv).map_err(|e| format!("{}: {}", path, e))
+}
+
+#[tauri::command(async)]
+pub fn export_summary_json(path: String, state: State<'_, AppState>) -> Result<(), String> {
+    let json = state.with(|g| serde_json::to_vec_pretty(&g.stats()).map_err(|e| e.to_string()))?;
+    fs::write(&path, json).map_err(|e| format!("{}: {}", path, e))
+}
+
+/// Guarda la sesión completa como proyecto `.pna`.
+#[tauri::command(async)]
+pub fn export_project(path: String, state: State<'_, AppState>) -> Result<(), String> {
+    let bytes = state.with(|g| g.state.to_bytes().map_err(|e| e.to_string()))?;
+    fs::write(&path, bytes).map_err(|e| format!("{}: {}", path, e))
+}
+
+#[tauri::command(async)]
+pub fn open_project(path: String, state: State<'_, AppState>) -> Result<GradeStatsOwned, String> {
+    let bytes = fs::read(&path).map_err(|e| format!("{}: {}", path, e))?;
+    let gradebook = OpenGradebook {
+        path: None,
+        state: EngineState::from_bytes(&bytes).map_err(|e| e.to_string())?,
+    };
+    let stats = gradebook.stats();
+    *state.lock()? = Some(gradebook);
+    Ok(stats)
+}
+
+/// Vigila un libro o una carpeta. Los cambios llegan como eventos `gradebook-changed`
+/// (el libro abierto se recargó), `gradebook-file-changed` y `gradebook-watch-error`.
+#[tauri::command]
+pub fn watch_path(path: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let path = fs::canonicalize(&path).map_err(|e| format!("{}: {}", path, e))?;
+    let watcher = watcher::watch(app, &path)?;
+    *state.watcher()? = Some(watcher);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn unwatch_path(state: State<'_, AppState>) -> Result<(), String> {
+    *state.watcher()? = None;
+    Ok(())
+}
+
+type LibraryState<'a> = State<'a, Mutex<Library>>;
+
+fn lock_library<'a>(library: &'a LibraryState<'_>) -> Result<std::sync::MutexGuard<'a, Library>, String> {
+    library.lock().map_err(|_| "library is unavailable".to_string())
+}
+
+/// Guarda el libro abierto en la biblioteca junto con un snapshot del ciclo para el historial.
+#[tauri::command(async)]
+pub fn save_to_library(key: CourseKey, state: State<'_, AppState>, library: LibraryState<'_>) -> Result<i64, String> {
+    let (source_path, engine_state) = state.with(|g| {
+        Ok((g.path.as_ref().map(|p| p.display().to_string()), g.state.clone()))
+    })?;
+    let summary = engine_state.stats().summary();
+    // Cada sección lleva su propio historial
+    let course = match key.section.as_str() {
+        "" => key.course.clone(),
+        section => format!("{} | {}", key.course, section),
+    };
+    let snapshot = TermSnapshot::new(course, key.term.clone(), summary, &engine_state.policy);
+
+    let mut library = lock_library(&library)?;
+    let id = library.save(&key, source_path.as_deref(), &engine_state)?;
+    library.record_snapshot(&snapshot)?;
+    Ok(id)
+}
+
+#[tauri::command(async)]
+pub fn open_from_library(id: i64, state: State<'_, AppState>, library: LibraryState<'_>) -> Result<GradeStatsOwned, String> {
+    let (_, engine_state) = lock_library(&library)?.load(id)?;
+    let gradebook = OpenGradebook { path: None, state: engine_state };
+    let stats = gradebook.stats();
+    *state.lock()? = Some(gradebook);
+    Ok(stats)
+}
+
+#[tauri::command]
+pub fn delete_from_library(id: i64, library: LibraryState<'_>) -> Result<(), String> {
+    lock_library(&library)?.delete(id)
+}
+
+#[tauri::command]
+pub fn library_terms(library: LibraryState<'_>) -> Result<Vec<String>, String> {
+    lock_library(&library)?.terms()
+}
+
+#[tauri::command]
+pub fn courses_in_term(term: String, library: LibraryState<'_>) -> Result<Vec<LibraryEntry>, String> {
+    lock_library(&library)?.courses_in_term(&term)
+}
+
+#[tauri::command]
+pub fn student_history(carnet: String, library: LibraryState<'_>) -> Result<Vec<StudentTerm>, String> {
+    lock_library(&library)?.student_history(&carnet)
+}
+
+#[derive(Serialize)]
+pub struct CourseTrends {
+    pub pass_rate: Trend,
+    pub inflation: Trend,
+    pub difficulty: Vec<EvaluationDrift>,
+}
+
+/// Tendencias entre ciclos; `course` es el curso, o "curso | sección" para una sección.
+#[tauri::command]
+pub fn course_trends(course: String, library: LibraryState<'_>) -> Result<CourseTrends, String> {
+    let history = lock_library(&library)?.history(&course)?;
+    Ok(CourseTrends {
+        pass_rate: history.pass_rate_trend(&course),
+        inflation: history.inflation_trend(&course),
+        difficulty: history.difficulty_drift(&course),
+    })
+}
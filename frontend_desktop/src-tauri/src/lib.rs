@@ -1,14 +1,49 @@
-// Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
-#[tauri::command]
-fn greet(name: &str) -> String {
-    format!("Hello, {}! You've been greeted from Rust!", name)
-}
+mod commands;
+mod library;
+mod state;
+mod watcher;
+
+use std::fs;
+use std::sync::Mutex;
+
+use library::Library;
+use state::AppState;
+use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![greet])
+        .manage(AppState::default())
+        .setup(|app| {
+            // La biblioteca vive en la carpeta de datos de la app, nunca sale del equipo
+            let folder = app.path().app_data_dir()?;
+            fs::create_dir_all(&folder)?;
+            app.manage(Mutex::new(Library::open(&folder.join("library.sqlite3"))?));
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            commands::open_gradebook,
+            commands::load_gradebook,
+            commands::get_summary,
+            commands::get_table,
+            commands::get_description_headers,
+            commands::get_policy,
+            commands::set_policy,
+            commands::export_csv,
+            commands::export_summary_json,
+            commands::export_project,
+            commands::open_project,
+            commands::watch_path,
+            commands::unwatch_path,
+            commands::save_to_library,
+            commands::open_from_library,
+            commands::delete_from_library,
+            commands::library_terms,
+            commands::courses_in_term,
+            commands::student_history,
+            commands::course_trends,
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
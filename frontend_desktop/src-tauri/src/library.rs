@@ -0,0 +1,393 @@
+//! Biblioteca local de la app de escritorio: libros importados, reglamentos, anotaciones y
+//! snapshots por ciclo en una base SQLite que no sale de la máquina del docente.
+
+use std::path::Path;
+
+use pna_core::api::{
+    AcademicStatus, Annotation, EngineState, GradingPolicy, HistoryStore, TermSnapshot,
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Esquema en orden; `PRAGMA user_version` guarda cuántas se aplicaron.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE gradebooks (
+        id INTEGER PRIMARY KEY,
+        course TEXT NOT NULL,
+        section TEXT NOT NULL DEFAULT '',
+        term TEXT NOT NULL,
+        source_path TEXT,
+        imported_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        description_headers TEXT NOT NULL,
+        grades TEXT NOT NULL,
+        attendance TEXT,
+        makeup_grades TEXT NOT NULL,
+        UNIQUE (course, section, term)
+    );
+    CREATE TABLE policies (
+        gradebook_id INTEGER PRIMARY KEY REFERENCES gradebooks(id) ON DELETE CASCADE,
+        policy TEXT NOT NULL
+    );
+    CREATE TABLE annotations (
+        id INTEGER PRIMARY KEY,
+        gradebook_id INTEGER NOT NULL REFERENCES gradebooks(id) ON DELETE CASCADE,
+        carnet TEXT,
+        evaluation TEXT,
+        text TEXT NOT NULL
+    );
+    CREATE TABLE enrollments (
+        gradebook_id INTEGER NOT NULL REFERENCES gradebooks(id) ON DELETE CASCADE,
+        carnet TEXT NOT NULL,
+        name TEXT NOT NULL,
+        accumulated_score REAL,
+        status TEXT NOT NULL,
+        PRIMARY KEY (gradebook_id, carnet)
+    );
+    CREATE INDEX enrollments_carnet ON enrollments(carnet);
+    CREATE TABLE term_snapshots (
+        id INTEGER PRIMARY KEY,
+        course TEXT NOT NULL,
+        term TEXT NOT NULL,
+        progress REAL NOT NULL,
+        snapshot TEXT NOT NULL
+    );
+    CREATE INDEX term_snapshots_course ON term_snapshots(course, term);",
+];
+
+// CourseKey, LibraryEntry y StudentTerm se repiten a mano en src/services/engine.ts:
+// si cambia uno, cambiar el otro.
+
+/// Dónde va un libro dentro de la biblioteca.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CourseKey {
+    pub course: String,
+    #[serde(default)]
+    pub section: String,
+    pub term: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryEntry {
+    pub id: i64,
+    pub key: CourseKey,
+    pub source_path: Option<String>,
+    pub imported_at: String,
+    pub student_count: usize,
+}
+
+/// One course a student took, as it stood when the gradebook was last saved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudentTerm {
+    pub gradebook_id: i64,
+    pub key: CourseKey,
+    pub name: String,
+    pub accumulated_score: Option<f32>,
+    pub status: AcademicStatus,
+}
+
+pub struct Library {
+    conn: Connection,
+}
+
+impl Library {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        Self::init(Connection::open(path).map_err(|e| format!("{}: {}", path.display(), e))?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, String> {
+        Self::init(Connection::open_in_memory().map_err(sql)?)
+    }
+
+    fn init(conn: Connection) -> Result<Self, String> {
+        conn.pragma_update(None, "foreign_keys", true).map_err(sql)?;
+        let mut library = Self { conn };
+        library.migrate()?;
+        Ok(library)
+    }
+
+    fn migrate(&mut self) -> Result<(), String> {
+        let version: usize = self.conn.pragma_query_value(None, "user_version", |row| row.get(0)).map_err(sql)?;
+        if version > MIGRATIONS.len() {
+            return Err(format!("library schema version {} is newer than this app ({})", version, MIGRATIONS.len()));
+        }
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+            let tx = self.conn.transaction().map_err(sql)?;
+            tx.execute_batch(migration).map_err(sql)?;
+            tx.pragma_update(None, "user_version", index + 1).map_err(sql)?;
+            tx.commit().map_err(sql)?;
+        }
+        Ok(())
+    }
+
+    /// Guarda el libro; si ya hay uno con el mismo curso, sección y ciclo, lo reemplaza.
+    pub fn save(&mut self, key: &CourseKey, source_path: Option<&str>, state: &EngineState) -> Result<i64, String> {
+        let stats = state.stats();
+        let tx = self.conn.transaction().map_err(sql)?;
+
+        let id: i64 = tx.query_row(
+            "INSERT INTO gradebooks (course, section, term, source_path, description_headers, grades, attendance, makeup_grades)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT (course, section, term) DO UPDATE SET
+                source_path = excluded.source_path,
+                imported_at = CURRENT_TIMESTAMP,
+                description_headers = excluded.description_headers,
+                grades = excluded.grades,
+                attendance = excluded.attendance,
+                makeup_grades = excluded.makeup_grades
+             RETURNING id",
+            params![
+                key.course,
+                key.section,
+                key.term,
+                source_path,
+                to_json(&state.description_headers)?,
+                to_json(&state.table)?,
+                state.attendance.as_ref().map(to_json).transpose()?,
+                to_json(&state.makeup_grades)?,
+            ],
+            |row| row.get(0),
+        ).map_err(sql)?;
+
+        tx.execute(
+            "INSERT INTO policies (gradebook_id, policy) VALUES (?1, ?2)
+             ON CONFLICT (gradebook_id) DO UPDATE SET policy = excluded.policy",
+            params![id, to_json(&state.policy)?],
+        ).map_err(sql)?;
+
+        tx.execute("DELETE FROM annotations WHERE gradebook_id = ?1", [id]).map_err(sql)?;
+        for annotation in &state.annotations {
+            tx.execute(
+                "INSERT INTO annotations (gradebook_id, carnet, evaluation, text) VALUES (?1, ?2, ?3, ?4)",
+                params![id, annotation.carnet, annotation.evaluation, annotation.text],
+            ).map_err(sql)?;
+        }
+
+        tx.execute("DELETE FROM enrollments WHERE gradebook_id = ?1", [id]).map_err(sql)?;
+        for (record, student) in state.table.records.iter().zip(stats.student_summaries()) {
+            tx.execute(
+                "INSERT OR REPLACE INTO enrollments (gradebook_id, carnet, name, accumulated_score, status) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![id, record.carnet.trim(), record.name, student.accumulated_score, format!("{:?}", student.status)],
+            ).map_err(sql)?;
+        }
+
+        tx.commit().map_err(sql)?;
+        Ok(id)
+    }
+
+    pub fn load(&self, id: i64) -> Result<(CourseKey, EngineState), String> {
+        let row = self.conn.query_row(
+            "SELECT g.course, g.section, g.term, g.description_headers, g.grades, g.attendance, g.makeup_grades, p.policy
+             FROM gradebooks g LEFT JOIN policies p ON p.gradebook_id = g.id
+             WHERE g.id = ?1",
+            [id],
+            |row| Ok((
+                CourseKey { course: row.get(0)?, section: row.get(1)?, term: row.get(2)? },
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, String>(6)?,
+                row.get::<_, Option<String>>(7)?,
+            )),
+        ).optional().map_err(sql)?;
+        let Some((key, headers, grades, attendance, makeup_grades, policy)) = row else {
+            return Err(format!("gradebook {} is not in the library", id));
+        };
+
+        let mut statement = self.conn
+            .prepare("SELECT carnet, evaluation, text FROM annotations WHERE gradebook_id = ?1 ORDER BY id")
+            .map_err(sql)?;
+        let annotations = statement
+            .query_map([id], |row| Ok(Annotation { carnet: row.get(0)?, evaluation: row.get(1)?, text: row.get(2)? }))
+            .map_err(sql)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sql)?;
+
+        let state = EngineState {
+            description_headers: from_json(&headers)?,
+            table: from_json(&grades)?,
+            policy: policy.as_deref().map(from_json).transpose()?.unwrap_or_else(GradingPolicy::default),
+            attendance: attendance.as_deref().map(from_json).transpose()?,
+            makeup_grades: from_json(&makeup_grades)?,
+            annotations,
+        };
+        Ok((key, state))
+    }
+
+    pub fn delete(&mut self, id: i64) -> Result<(), String> {
+        self.conn.execute("DELETE FROM gradebooks WHERE id = ?1", [id]).map_err(sql)?;
+        Ok(())
+    }
+
+    /// Ciclos con algún libro guardado, el más reciente primero.
+    pub fn terms(&self) -> Result<Vec<String>, String> {
+        let mut statement = self.conn.prepare("SELECT DISTINCT term FROM gradebooks ORDER BY term DESC").map_err(sql)?;
+        let terms = statement.query_map([], |row| row.get(0)).map_err(sql)?;
+        terms.collect::<Result<Vec<String>, _>>().map_err(sql)
+    }
+
+    /// Todos los cursos (y secciones) de un ciclo.
+    pub fn courses_in_term(&self, term: &str) -> Result<Vec<LibraryEntry>, String> {
+        let mut statement = self.conn.prepare(
+            "SELECT g.id, g.course, g.section, g.term, g.source_path, g.imported_at,
+                    (SELECT COUNT(*) FROM enrollments e WHERE e.gradebook_id = g.id)
+             FROM gradebooks g WHERE g.term = ?1
+             ORDER BY g.course, g.section",
+        ).map_err(sql)?;
+        let entries = statement.query_map([term], |row| Ok(LibraryEntry {
+            id: row.get(0)?,
+            key: CourseKey { course: row.get(1)?, section: row.get(2)?, term: row.get(3)? },
+            source_path: row.get(4)?,
+            imported_at: row.get(5)?,
+            student_count: row.get(6)?,
+        })).map_err(sql)?;
+        entries.collect::<Result<Vec<_>, _>>().map_err(sql)
+    }
+
+    /// Cursos en que aparece el carnet, en orden de ciclo.
+    pub fn student_history(&self, carnet: &str) -> Result<Vec<StudentTerm>, String> {
+        let mut statement = self.conn.prepare(
+            "SELECT g.id, g.course, g.section, g.term, e.name, e.accumulated_score, e.status
+             FROM enrollments e JOIN gradebooks g ON g.id = e.gradebook_id
+             WHERE e.carnet = ?1 COLLATE NOCASE
+             ORDER BY g.term, g.course",
+        ).map_err(sql)?;
+        let rows = statement.query_map([carnet.trim()], |row| Ok((
+            row.get::<_, i64>(0)?,
+            CourseKey { course: row.get(1)?, section: row.get(2)?, term: row.get(3)? },
+            row.get::<_, String>(4)?,
+            row.get::<_, Option<f32>>(5)?,
+            row.get::<_, String>(6)?,
+        ))).map_err(sql)?;
+
+        rows.map(|row| {
+            let (gradebook_id, key, name, accumulated_score, status) = row.map_err(sql)?;
+            Ok(StudentTerm {
+                gradebook_id,
+                key,
+                name,
+                accumulated_score,
+                status: serde_json::from_value(serde_json::Value::String(status)).map_err(|e| e.to_string())?,
+            })
+        }).collect()
+    }
+
+    /// Guarda un snapshot; reemplaza al del mismo curso, ciclo y avance (como `HistoryStore::record`).
+    pub fn record_snapshot(&mut self, snapshot: &TermSnapshot) -> Result<(), String> {
+        let tx = self.conn.transaction().map_err(sql)?;
+        tx.execute(
+            "DELETE FROM term_snapshots WHERE course = ?1 AND term = ?2 AND abs(progress - ?3) < 1e-6",
+            params![snapshot.course, snapshot.term, snapshot.progress],
+        ).map_err(sql)?;
+        tx.execute(
+            "INSERT INTO term_snapshots (course, term, progress, snapshot) VALUES (?1, ?2, ?3, ?4)",
+            params![snapshot.course, snapshot.term, snapshot.progress, to_json(snapshot)?],
+        ).map_err(sql)?;
+        tx.commit().map_err(sql)
+    }
+
+    /// Historial del curso, listo para las tendencias de `HistoryStore`.
+    pub fn history(&self, course: &str) -> Result<HistoryStore, String> {
+        let mut statement = self.conn
+            .prepare("SELECT snapshot FROM term_snapshots WHERE course = ?1 ORDER BY id")
+            .map_err(sql)?;
+        let rows = statement.query_map([course], |row| row.get::<_, String>(0)).map_err(sql)?;
+
+        let mut store = HistoryStore::new();
+        for json in rows {
+            store.record(from_json(&json.map_err(sql)?)?);
+        }
+        Ok(store)
+    }
+}
+
+fn sql(e: rusqlite::Error) -> String {
+    e.to_string()
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|e| e.to_string())
+}
+
+fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, String> {
+    serde_json::from_str(json).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pna_core::api::AcademicTable;
+    use pna_core::models::gradebook::{GradeValue, StudentRecord};
+
+    /// Filas `(carnet, [parcial, final])`, ambas sobre 50 puntos.
+    fn state(rows: &[(&str, [f32; 2])]) -> EngineState {
+        let table = AcademicTable {
+            evaluations: vec!["Parcial".to_string(), "Final".to_string()],
+            records: rows.iter().map(|(carnet, scores)| StudentRecord {
+                carnet: carnet.to_string(),
+                name: format!("Estudiante {}", carnet),
+                email: String::new(),
+                group: "IMS".to_string(),
+                section: None,
+                grades: scores.iter().map(|&obtained| GradeValue::Fraction { obtained, total: 50.0 }).collect(),
+                final_grade: GradeValue::Absent,
+            }).collect(),
+        };
+        EngineState {
+            description_headers: vec!["Cálculo I".to_string()],
+            table,
+            policy: GradingPolicy { passing_score: 60.0, ..GradingPolicy::default() },
+            attendance: None,
+            makeup_grades: Vec::new(),
+            annotations: vec![Annotation { carnet: Some("A1".to_string()), evaluation: None, text: "Beca".to_string() }],
+        }
+    }
+
+    fn key(course: &str, term: &str) -> CourseKey {
+        CourseKey { course: course.to_string(), section: "A".to_string(), term: term.to_string() }
+    }
+
+    #[test]
+    fn test_save_load_and_queries() {
+        let mut library = Library::open_in_memory().unwrap();
+        let first = library.save(&key("Cálculo I", "2024-1"), None, &state(&[("A1", [20.0, 20.0]), ("A2", [40.0, 45.0])])).unwrap();
+        library.save(&key("Cálculo II", "2024-2"), None, &state(&[("A1", [35.0, 40.0])])).unwrap();
+
+        // Volver a guardar el mismo curso reemplaza el libro
+        let again = library.save(&key("Cálculo I", "2024-1"), Some("/notas.xlsx"), &state(&[("A1", [30.0, 20.0])])).unwrap();
+        assert_eq!(first, again);
+
+        let (saved_key, restored) = library.load(first).unwrap();
+        assert_eq!(saved_key, key("Cálculo I", "2024-1"));
+        assert_eq!(restored.table.records.len(), 1);
+        assert_eq!(restored.policy.passing_score, 60.0);
+        assert_eq!(restored.annotations[0].text, "Beca");
+
+        assert_eq!(library.terms().unwrap(), vec!["2024-2", "2024-1"]);
+        let courses = library.courses_in_term("2024-1").unwrap();
+        assert_eq!((courses.len(), courses[0].student_count), (1, 1));
+
+        let history = library.student_history("a1").unwrap();
+        let terms: Vec<&str> = history.iter().map(|t| t.key.term.as_str()).collect();
+        assert_eq!(terms, vec!["2024-1", "2024-2"]);
+        assert_eq!(history[1].status, AcademicStatus::Approved);
+
+        library.delete(first).unwrap();
+        assert!(library.load(first).is_err());
+        assert_eq!(library.student_history("A1").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_snapshots_feed_history() {
+        let mut library = Library::open_in_memory().unwrap();
+        for (term, rows) in [("2024-1", ("A1", [20.0, 20.0])), ("2024-2", ("B1", [40.0, 45.0]))] {
+            let state = state(&[rows]);
+            let summary = state.stats().summary();
+            library.record_snapshot(&TermSnapshot::new("Cálculo I", term, summary, &state.policy)).unwrap();
+        }
+
+        let rates: Vec<f32> = library.history("Cálculo I").unwrap()
+            .pass_rate_trend("Cálculo I").terms.iter().map(|t| t.value).collect();
+        assert_eq!(rates, vec![0.0, 1.0]);
+    }
+}
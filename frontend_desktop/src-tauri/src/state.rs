@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+use pna_core::api::{diff_with_stats, EngineState, GradebookDiff, GradeStats, GradeStatsOwned};
+
+use crate::watcher::GradebookWatcher;
+
+/// Libro abierto en la app de escritorio. Guarda sólo los datos de entrada (igual que un
+/// archivo `.pna`); las estadísticas se recalculan en cada consulta.
+pub struct OpenGradebook {
+    /// Archivo de origen, si vino del disco.
+    pub path: Option<PathBuf>,
+    pub state: EngineState,
+}
+
+impl OpenGradebook {
+    pub fn from_bytes(data: &[u8], path: Option<PathBuf>) -> Result<Self, String> {
+        let state = EngineState::from_workbook(data).map_err(|e| e.to_string())?;
+        Ok(Self { path, state })
+    }
+
+    pub fn stats(&self) -> GradeStatsOwned {
+        GradeStatsOwned::from(&self.state.stats())
+    }
+
+    /// Reemplaza las notas con una nueva versión del archivo, conservando el reglamento,
+    /// las notas de reposición y las anotaciones. Devuelve qué cambió.
+    pub fn reload(&mut self, data: &[u8]) -> Result<GradebookDiff, String> {
+        let EngineState { description_headers, table, attendance, .. } = EngineState::from_workbook(data).map_err(|e| e.to_string())?;
+        let new_stats = GradeStats::with_attendance(&table, &self.state.policy, attendance.as_ref())
+            .with_makeup(&self.state.makeup_grades);
+        let diff = diff_with_stats(&self.state.stats(), &new_stats);
+        self.state.description_headers = description_headers;
+        self.state.table = table;
+        self.state.attendance = attendance;
+        Ok(diff)
+    }
+}
+
+#[derive(Default)]
+pub struct AppState {
+    gradebook: Mutex<Option<OpenGradebook>>,
+    /// Vigilancia activa; al reemplazarla o soltarla se detiene la anterior.
+    watcher: Mutex<Option<GradebookWatcher>>,
+}
+
+impl AppState {
+    pub fn lock(&self) -> Result<MutexGuard<'_, Option<OpenGradebook>>, String> {
+        self.gradebook.lock().map_err(|_| "engine state is unavailable".to_string())
+    }
+
+    pub fn watcher(&self) -> Result<MutexGuard<'_, Option<GradebookWatcher>>, String> {
+        self.watcher.lock().map_err(|_| "watcher state is unavailable".to_string())
+    }
+
+    /// Ejecuta `f` sobre el libro abierto, o falla si no hay ninguno.
+    pub fn with<T>(&self, f: impl FnOnce(&mut OpenGradebook) -> Result<T, String>) -> Result<T, String> {
+        let mut guard = self.lock()?;
+        let gradebook = guard.as_mut().ok_or_else(|| "no gradebook is open".to_string())?;
+        f(gradebook)
+    }
+}
@@ -0,0 +1,122 @@
+//! Vigila el libro abierto (o la carpeta donde está) y lo recarga cuando cambia en disco.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use pna_core::api::GradebookDiff;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::state::AppState;
+
+/// Synced folders write a file in several steps; wait until they settle before parsing.
+const DEBOUNCE: Duration = Duration::from_millis(750);
+const GRADEBOOK_EXTENSIONS: &[&str] = &["xlsx", "xlsm", "xls", "ods"];
+
+/// The open gradebook was reloaded and something changed.
+pub const GRADEBOOK_CHANGED: &str = "gradebook-changed";
+/// Another gradebook in the watched folder changed.
+pub const FILE_CHANGED: &str = "gradebook-file-changed";
+pub const WATCH_ERROR: &str = "gradebook-watch-error";
+
+pub type GradebookWatcher = Debouncer<RecommendedWatcher>;
+
+#[derive(Clone, Serialize)]
+pub struct GradebookChanged {
+    pub path: PathBuf,
+    /// Carnets with at least one modified grade.
+    pub changed_students: Vec<String>,
+    pub diff: GradebookDiff,
+}
+
+#[derive(Clone, Serialize)]
+pub struct FileChanged {
+    pub path: PathBuf,
+}
+
+#[derive(Clone, Serialize)]
+pub struct WatchError {
+    pub path: Option<PathBuf>,
+    pub message: String,
+}
+
+/// Empieza a vigilar `path`, que debe ser absoluto y canónico. Un archivo se vigila a través de
+/// su carpeta: los editores y las apps de sincronización lo reemplazan en vez de escribirlo.
+pub fn watch(app: AppHandle, path: &Path) -> Result<GradebookWatcher, String> {
+    let (folder, only) = if path.is_dir() {
+        (path.to_path_buf(), None)
+    } else {
+        let folder = path.parent().ok_or_else(|| format!("{}: no parent folder", path.display()))?;
+        (folder.to_path_buf(), Some(path.to_path_buf()))
+    };
+
+    let mut debouncer = new_debouncer(DEBOUNCE, move |result: DebounceEventResult| match result {
+        Ok(events) => {
+            let mut paths: Vec<PathBuf> = events.into_iter()
+                .map(|event| event.path)
+                .filter(|p| is_gradebook(p) && only.as_ref().is_none_or(|only| only == p))
+                .collect();
+            paths.sort();
+            paths.dedup();
+            for path in paths {
+                on_change(&app, path);
+            }
+        }
+        Err(e) => {
+            let _ = app.emit(WATCH_ERROR, WatchError { path: None, message: e.to_string() });
+        }
+    }).map_err(|e| e.to_string())?;
+
+    debouncer.watcher()
+        .watch(&folder, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("{}: {}", folder.display(), e))?;
+    Ok(debouncer)
+}
+
+fn is_gradebook(path: &Path) -> bool {
+    let is_lock_file = path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("~$") || name.starts_with(".~lock"));
+    let extension = path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase);
+    !is_lock_file && extension.is_some_and(|e| GRADEBOOK_EXTENSIONS.contains(&e.as_str()))
+}
+
+fn on_change(app: &AppHandle, path: PathBuf) {
+    // Borrado o a medio reemplazar: cuando termine llega otro evento
+    let Ok(data) = fs::read(&path) else { return };
+
+    let state = app.state::<AppState>();
+    let result = {
+        let mut guard = match state.lock() {
+            Ok(guard) => guard,
+            Err(message) => {
+                let _ = app.emit(WATCH_ERROR, WatchError { path: Some(path), message });
+                return;
+            }
+        };
+        match guard.as_mut().filter(|g| g.path.as_deref() == Some(path.as_path())) {
+            Some(gradebook) => gradebook.reload(&data),
+            None => {
+                drop(guard);
+                let _ = app.emit(FILE_CHANGED, FileChanged { path });
+                return;
+            }
+        }
+    };
+
+    match result {
+        Ok(diff) if diff.is_empty() => {}
+        Ok(diff) => {
+            let mut changed_students: Vec<String> = diff.changed_cells.iter().map(|c| c.carnet.clone()).collect();
+            changed_students.dedup();
+            let _ = app.emit(GRADEBOOK_CHANGED, GradebookChanged { path, changed_students, diff });
+        }
+        // El archivo anterior sigue cargado
+        Err(message) => {
+            let _ = app.emit(WATCH_ERROR, WatchError { path: Some(path), message });
+        }
+    }
+}
@@ -58,7 +58,7 @@ export function ClassDashboard({ data }: ClassDashboardProps) {
             <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-5 gap-4">
                 <StatCard
                     title="Promedio General"
-                    value={metrics.overall_average.toFixed(2)}
+                    value={metrics.overall_average?.toFixed(2) || "N/A"}
                     subtext="Calificación media del curso"
                     icon={TrendingUp}
                     colorClass="text-indigo-600"
@@ -141,10 +141,10 @@ export function ClassDashboard({ data }: ClassDashboardProps) {
                         <div className="flex-1">
                             <h3 className="text-lg font-bold text-indigo-900 mb-1">Resumen General</h3>
                             <p className="text-slate-700 text-sm mb-2">
-                                {metrics.overall_average > 70
+                                {(metrics.overall_average ?? 0) > 70
                                     ? "La clase muestra un rendimiento sólido general."
                                     : "El promedio de la clase indica áreas de oportunidad importantes."}
-                                {metrics.overall_std_dev > 15
+                                {(metrics.overall_std_dev ?? 0) > 15
                                     ? " Existe una alta variabilidad entre los estudiantes, sugiriendo brechas de conocimiento."
                                     : " El grupo es bastante homogéneo en su rendimiento."}
                             </p>
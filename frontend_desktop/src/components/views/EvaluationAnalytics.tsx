@@ -114,7 +114,7 @@ export function EvaluationAnalytics({ data }: EvaluationAnalyticsProps) {
                                 <TableCell className="font-medium">{ev.name}</TableCell>
                                 <TableCell>
                                     <div className="flex flex-col">
-                                        <span className="font-bold text-slate-700">{ev.average.toFixed(2)}</span>
+                                        <span className="font-bold text-slate-700">{ev.average?.toFixed(2) || 'N/A'}</span>
                                     </div>
                                 </TableCell>
                                 <TableCell className="font-mono text-slate-500">{ev.std_dev?.toFixed(2) || 'N/A'}</TableCell>
@@ -119,7 +119,7 @@ export function EvaluationDetailSheet({
                             <Card>
                                 <CardContent className="p-4 pt-5 text-center">
                                     <div className="text-xs font-bold text-slate-400 uppercase tracking-wider mb-1">Promedio</div>
-                                    <div className="text-2xl font-black text-slate-900">{evaluation.average.toFixed(2)}</div>
+                                    <div className="text-2xl font-black text-slate-900">{evaluation.average?.toFixed(2) || "N/A"}</div>
                                 </CardContent>
                             </Card>
                             <Card>
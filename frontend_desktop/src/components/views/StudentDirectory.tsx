@@ -153,7 +153,7 @@ export function StudentDirectory({ data }: StudentDirectoryProps) {
                                 <TableCell className="font-mono text-xs text-slate-500">{student.id}</TableCell>
                                 <TableCell className="font-medium">{student.name}</TableCell>
                                 <TableCell>{student.accumulated_score}</TableCell>
-                                <TableCell>{student.percentile.toFixed(2)}%</TableCell>
+                                <TableCell>{student.percentile != null ? `${student.percentile.toFixed(2)}%` : 'N/A'}</TableCell>
                                 <TableCell className="text-right">
                                     <Badge variant={
                                         student.status === 'Approved' ? 'success' :
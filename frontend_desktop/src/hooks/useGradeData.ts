@@ -1,48 +1,27 @@
-import { useState, useCallback } from 'react';
-import { createEngine, initEngine } from '../services/engine';
-
-export interface Student {
-    id: string;
-    name: string;
-    accumulated_score: number;
-    percentile: number;
-    std_dev: number;
-    status: 'Approved' | 'Failed' | 'OnTrack' | 'Warning' | 'Critical';
-}
-
-export interface Evaluation {
-    id: string;
-    name: string;
-    average: number;
-    std_dev: number;
-    highest_score: number;
-    lowest_score: number;
-    max_possible_score: number;
-    evaluated_count: number;
-    missing_count: number;
-}
-
-export interface ClassSummary {
-    student_count: number;
-    overall_average: number;
-    overall_std_dev: number;
-    approved_count: number;
-    warning_count: number;
-    on_track_count: number;
-    critical_count: number;
-    failed_count: number;
-}
-
-export interface GradeData {
-    students: Student[];
-    evaluations: Evaluation[];
-    class: ClassSummary;
-}
+import { useState, useCallback, useEffect } from 'react';
+import {
+    type GradebookChanged,
+    getDescriptionHeaders,
+    getSummary,
+    getTable,
+    loadGradebook,
+    onGradebookChanged,
+    onWatchError,
+    openGradebook,
+    watchPath
+} from '../services/engine';
+import type { AcademicTable, ClassSummary, EvaluationSummary, GradeStatsOwned, StudentSummary } from '../../../frontend_web/pkg/pna_wasm.js';
+
+// Tipos generados desde los modelos de Rust (pna_core, feature `ts`), los mismos del frontend web
+export type Student = StudentSummary;
+export type Evaluation = EvaluationSummary;
+export type { ClassSummary };
+export type GradeData = GradeStatsOwned;
 
 export interface ExtendedAnalysis {
     description_headers: string[];
     summary: GradeData;
-    table: any; // Raw table data
+    table: AcademicTable;
     distributions: {
         range: string;
         count: number;
@@ -53,7 +32,7 @@ export interface ExtendedAnalysis {
 function getDynamicDistributions(students: Student[]) {
     // 1. Encontrar la nota más alta actual en la clase
     // Si nadie tiene nota, asumimos 1 para evitar división por cero
-    const currentMaxScore = Math.max(...students.map(s => s.accumulated_score), 0) || 10;
+    const currentMaxScore = Math.max(...students.map(s => s.accumulated_score ?? 0), 0) || 10;
 
     let ranges = [];
 
@@ -91,7 +70,7 @@ function getDynamicDistributions(students: Student[]) {
     // 3. Mapear los estudiantes a los rangos calculados
     return ranges.map(r => {
         const studentsInBucket = students.filter((s: Student) => 
-            s.accumulated_score >= r.min && s.accumulated_score < r.max
+            (s.accumulated_score ?? 0) >= r.min && (s.accumulated_score ?? 0) < r.max
         );
         
         return {
@@ -109,45 +88,78 @@ export function useGradeData() {
     const [ready, setReady] = useState(false);
     const [loading, setLoading] = useState(false);
     const [data, setData] = useState<ExtendedAnalysis | null>(null);
+    const [lastChange, setLastChange] = useState<GradebookChanged | null>(null);
 
+    // El motor corre en el backend de Tauri: no hay nada que inicializar
     const init = useCallback(async () => {
-        await initEngine();
         setReady(true);
     }, []);
 
+    // Lee del backend el libro ya cargado
+    const refresh = useCallback(async (loaded?: GradeData) => {
+        const summary = loaded ?? await getSummary();
+        const table = await getTable();
+
+        // Calculate Distributions
+        const students = summary.students;
+
+        const distributions = getDynamicDistributions(students);
+
+        setData({
+            description_headers: await getDescriptionHeaders(),
+            summary,
+            table,
+            distributions
+        });
+    }, []);
+
     const processFile = useCallback(async (file: File) => {
         if (!ready) return;
         setLoading(true);
         try {
             const bytes = new Uint8Array(await file.arrayBuffer());
-            const ge = createEngine(bytes);
-
-            const summary = ge.get_summary() as GradeData;
-            const table = ge.get_table();
-
-            // Calculate Distributions
-            const students = summary.students;
-
-            const distributions = getDynamicDistributions(students);
-
-            setData({
-                description_headers: ge.get_description_headers(),
-                summary,
-                table,
-                distributions
-            });
+            await refresh(await loadGradebook(bytes));
         } catch (e) {
             console.error("Failed to process file", e);
         } finally {
             setLoading(false);
         }
-    }, [ready]);
+    }, [ready, refresh]);
+
+    // Abre un archivo del disco y lo recarga solo cuando cambia
+    const openPath = useCallback(async (path: string) => {
+        if (!ready) return;
+        setLoading(true);
+        try {
+            await refresh(await openGradebook(path));
+            await watchPath(path);
+        } catch (e) {
+            console.error("Failed to open file", e);
+        } finally {
+            setLoading(false);
+        }
+    }, [ready, refresh]);
+
+    useEffect(() => {
+        const unlisten = [
+            onGradebookChanged((change) => {
+                setLastChange(change);
+                refresh().catch((e) => console.error("Failed to reload file", e));
+            }),
+            onWatchError((error) => console.error("Failed to reload file", error.message)),
+        ];
+        return () => {
+            unlisten.forEach((p) => p.then((stop) => stop()));
+        };
+    }, [refresh]);
 
     return {
         ready,
         loading,
         data,
+        lastChange,
         init,
-        processFile
+        processFile,
+        openPath
     };
 }
@@ -1,91 +1,172 @@
 // services/engine.ts
-import init, { GradeEngine } from "../../pkg/pna_wasm.js";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+// Tipos generados desde los modelos de Rust (pna_core, feature `ts`) con scripts/wasm_compile.sh;
+// el backend de Tauri serializa los mismos structs.
+import type { AcademicStatus, AcademicTable, EvaluationDrift, GradebookDiff, GradeStatsOwned, GradingPolicy, Trend } from "../../../frontend_web/pkg/pna_wasm.js";
 
-let ready = false;
+/**
+ * Engine commands
+ *
+ * The desktop app runs `pna_core` natively in the Tauri backend (src-tauri/src/commands.rs).
+ * The opened gradebook lives in Tauri managed state, so after loading a file the other
+ * commands read from it without sending the data back and forth.
+ *
+ * Usage example:
+ *
+ * ```ts
+ * const summary = await openGradebook("/home/me/notas.xlsx"); // or loadGradebook(bytes)
+ * const table = await getTable();
+ * await exportCsv("/home/me/notas.csv");
+ * ```
+ *
+ * Loading and policy commands return the computed summary (GradeStatsOwned). Its fields
+ * are optional where the engine has no value; over Tauri they arrive as `null`, so check
+ * them with `!= null` rather than `!== undefined`.
+ *
+ * Every command rejects with the error message if there is no open gradebook or the
+ * file cannot be read.
+ */
+
+export function openGradebook(path: string) {
+    return invoke<GradeStatsOwned>("open_gradebook", { path });
+}
+
+/** For files dropped on the window, which arrive as bytes instead of a path. */
+export function loadGradebook(bytes: Uint8Array) {
+    return invoke<GradeStatsOwned>("load_gradebook", { bytes: Array.from(bytes) });
+}
+
+export function getSummary() {
+    return invoke<GradeStatsOwned>("get_summary");
+}
+
+export function getTable() {
+    return invoke<AcademicTable>("get_table");
+}
+
+export function getDescriptionHeaders() {
+    return invoke<string[]>("get_description_headers");
+}
 
-export async function initEngine() {
-    if (!ready) {
-        await init();
-        ready = true;
-    }
+export function getPolicy() {
+    return invoke<GradingPolicy>("get_policy");
 }
 
-export function createEngine(bytes: Uint8Array) {
-    if (!ready) {
-        throw new Error("Engine not initialized");
-    }
-    return new GradeEngine(bytes);
+export function setPolicy(policy: GradingPolicy) {
+    return invoke<GradeStatsOwned>("set_policy", { policy });
+}
+
+export function exportCsv(path: string) {
+    return invoke<void>("export_csv", { path });
+}
+
+export function exportSummaryJson(path: string) {
+    return invoke<void>("export_summary_json", { path });
+}
+
+/** Saves the full session (table, policy, attendance, annotations) as a `.pna` file. */
+export function exportProject(path: string) {
+    return invoke<void>("export_project", { path });
+}
+
+export function openProject(path: string) {
+    return invoke<GradeStatsOwned>("open_project", { path });
 }
 
 /**
- * GradeEngine
- * 
- * This class comes from a WebAssembly (WASM) module and acts as the main interface
- * to interact with the grade data. It exposes the class table and computed summaries.
- * 
- * Usage example:
- * 
- * ```ts
- * import { GradeEngine } from "../pkg/pkg.js";
- * 
- * const engine = new GradeEngine(csvBytes); // csvBytes is Uint8Array
- * const summary = engine.get_summary();     // JSON summary of class
- * const table = engine.get_table();         // JSON table of scores
- * ```
- * 
- * Methods:
- * 
- * 1. get_summary(): Returns a summary of the class in JSON format.
- * 
- *    Structure:
- *    {
- *      class: {
- *        student_count: number,       // Total number of students
- *        overall_average: number,     // Class average score
- *        overall_std_dev: number,     // Standard deviation of student averages
- *        approved_count: number,      // Number of students with status "Approved"
- *        at_risk_count: number,       // Number of students with status "AtRisk"
- *        failed_count: number         // Number of students with status "Failed"
- *      },
- *      students: [
- *        {
- *          id: string,               // Student index
- *          name: string,             // Student name
- *          average: number | null,   // Average score of student
- *          percentile: number | null,// Percentile rank of student in class
- *          std_dev: number | null,   // Standard deviation of student scores
- *          status: "Approved" | "AtRisk" | "Failed"
- *        },
- *        ...
- *      ],
- *      evaluations: [
- *        {
- *          id: string,               // Evaluation index
- *          name: string,             // Evaluation name
- *          average: number | null,   // Average score of this evaluation
- *          std_dev: number | null,   // Standard deviation of this evaluation
- *          highest_score: number | null, // Highest score
- *          lowest_score: number | null,  // Lowest score
- *          evaluated_count: number,      // Number of students evaluated
- *          missing_count: number         // Number of missing scores
- *        },
- *        ...
- *      ]
- *    }
- * 
- * 2. get_table(): Returns the raw grade table in JSON format.
- * 
- *    Structure:
- *    {
- *      students: string[],         // Array of student names
- *      evaluations: string[],      // Array of evaluation names
- *      scores: (number | null)[][] // 2D array of scores: [student][evaluation]
- *    }
- * 
- * Notes:
- * - Use get_summary() for building dashboards, charts, or quick analysis.
- * - Use get_table() if you need access to the raw scores for detailed comparisons or
- *   cross-analysis in the frontend.
- * - All methods return JSON-compatible objects; you can pass them directly to React state
- *   or charting libraries.
+ * Watches a gradebook file, or the folder it lives in, for changes on disk. When the open
+ * gradebook changes it is reloaded in the backend and `gradebook-changed` is emitted with
+ * the diff; other gradebooks in a watched folder emit `gradebook-file-changed`. Watching a
+ * new path replaces the previous watch.
+ */
+export function watchPath(path: string) {
+    return invoke<void>("watch_path", { path });
+}
+
+export function unwatchPath() {
+    return invoke<void>("unwatch_path");
+}
+
+export interface GradebookChanged {
+    path: string;
+    changed_students: string[];
+    diff: GradebookDiff;
+}
+
+export function onGradebookChanged(handler: (change: GradebookChanged) => void) {
+    return listen<GradebookChanged>("gradebook-changed", (event) => handler(event.payload));
+}
+
+export function onWatchError(handler: (error: { path: string | null; message: string }) => void) {
+    return listen<{ path: string | null; message: string }>("gradebook-watch-error", (event) => handler(event.payload));
+}
+
+/**
+ * Local library
+ *
+ * Gradebooks saved with `saveToLibrary` are kept in a SQLite database in the app data folder,
+ * together with their policy, annotations and a term snapshot for the course history.
+ * Saving the same course, section and term again replaces the stored gradebook.
  */
+
+// CourseKey, LibraryEntry y StudentTerm son los structs de src-tauri/src/library.rs:
+// si cambia uno, cambiar el otro.
+export interface CourseKey {
+    course: string;
+    section: string;
+    term: string;
+}
+
+export interface LibraryEntry {
+    id: number;
+    key: CourseKey;
+    source_path: string | null;
+    imported_at: string;
+    student_count: number;
+}
+
+export interface StudentTerm {
+    gradebook_id: number;
+    key: CourseKey;
+    name: string;
+    accumulated_score: number | null;
+    status: AcademicStatus;
+}
+
+export function saveToLibrary(key: CourseKey) {
+    return invoke<number>("save_to_library", { key });
+}
+
+export function openFromLibrary(id: number) {
+    return invoke<GradeStatsOwned>("open_from_library", { id });
+}
+
+export function deleteFromLibrary(id: number) {
+    return invoke<void>("delete_from_library", { id });
+}
+
+export function libraryTerms() {
+    return invoke<string[]>("library_terms");
+}
+
+/** All courses and sections saved for a term. */
+export function coursesInTerm(term: string) {
+    return invoke<LibraryEntry[]>("courses_in_term", { term });
+}
+
+/** Every saved course a student appears in, oldest term first. */
+export function studentHistory(carnet: string) {
+    return invoke<StudentTerm[]>("student_history", { carnet });
+}
+
+export interface CourseTrends {
+    pass_rate: Trend;
+    inflation: Trend;
+    difficulty: EvaluationDrift[];
+}
+
+/** Pass rate, grade inflation and evaluation difficulty across terms. */
+export function courseTrends(course: string) {
+    return invoke<CourseTrends>("course_trends", { course });
+}
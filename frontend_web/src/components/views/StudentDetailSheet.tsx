@@ -45,7 +45,7 @@ export function StudentDetailSheet({
             return {
                 exam: ev.name,
                 score: score,
-                classAverage: ev.average,
+                classAverage: ev.average ?? 0,
                 maxScore: ev.max_possible_score || 100
             };
         });
@@ -94,7 +94,7 @@ export function StudentDetailSheet({
                                     <span className="text-xs font-bold text-slate-400 uppercase tracking-wider flex items-center gap-1">
                                         <TrendingUp size={14} /> Puntaje
                                     </span>
-                                    <span className="text-3xl font-black text-slate-900">{student.accumulated_score.toFixed(2)}</span>
+                                    <span className="text-3xl font-black text-slate-900">{student.accumulated_score?.toFixed(2) || "N/A"}</span>
                                 </CardContent>
                             </Card>
                             <Card>
@@ -102,7 +102,7 @@ export function StudentDetailSheet({
                                     <span className="text-xs font-bold text-slate-400 uppercase tracking-wider flex items-center gap-1">
                                         <Award size={14} /> Percentil
                                     </span>
-                                    <span className="text-3xl font-black text-slate-700">{student.percentile.toFixed(2)}%</span>
+                                    <span className="text-3xl font-black text-slate-700">{student.percentile !== undefined ? `${student.percentile.toFixed(2)}%` : "N/A"}</span>
                                 </CardContent>
                             </Card>
                         </div>
@@ -160,7 +160,7 @@ export function StudentDirectory({ data }: StudentDirectoryProps) {
                                 <TableCell className="font-medium">{student.name}</TableCell>
                                 <TableCell>{student.accumulated_score}</TableCell>
                                 <TableCell className="text-center">{student.lost_points}</TableCell>
-                                <TableCell>{student.percentile.toFixed(2)}%</TableCell>
+                                <TableCell>{student.percentile !== undefined ? `${student.percentile.toFixed(2)}%` : 'N/A'}</TableCell>
                                 <TableCell className="text-right">
                                     <Badge variant={
                                         student.status === 'Approved' ? 'success' :
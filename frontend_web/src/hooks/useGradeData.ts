@@ -1,51 +1,17 @@
 import { useState, useCallback } from 'react';
 import { createEngine, initEngine } from '../services/engine';
+import type { AcademicTable, ClassSummary, EvaluationSummary, GradeStatsOwned, StudentSummary } from '../../pkg/pna_wasm.js';
 
-export interface Student {
-    id: string;
-    name: string;
-    accumulated_score: number;
-    lost_points: number;
-    percentile: number;
-    std_dev: number;
-    status: 'Approved' | 'Failed' | 'OnTrack' | 'Warning' | 'Critical';
-}
-
-export interface Evaluation {
-    id: string;
-    name: string;
-    average: number;
-    std_dev: number;
-    highest_score: number;
-    lowest_score: number;
-    max_possible_score: number;
-    evaluated_count: number;
-    missing_count: number;
-}
-
-export interface ClassSummary {
-    student_count: number;
-    acumulated_points: number;
-    overall_average: number;
-    overall_std_dev: number;
-    evaluation_count: number;
-    approved_count: number;
-    warning_count: number;
-    on_track_count: number;
-    critical_count: number;
-    failed_count: number;
-}
-
-export interface GradeData {
-    students: Student[];
-    evaluations: Evaluation[];
-    class: ClassSummary;
-}
+// Tipos generados desde los modelos de Rust (pna_core, feature `ts`) en pkg/pna_wasm.d.ts
+export type Student = StudentSummary;
+export type Evaluation = EvaluationSummary;
+export type { ClassSummary };
+export type GradeData = GradeStatsOwned;
 
 export interface ExtendedAnalysis {
     description_headers: string[];
     summary: GradeData;
-    table: any; // Raw table data
+    table: AcademicTable;
     distributions: {
         range: string;
         count: number;
@@ -56,7 +22,7 @@ export interface ExtendedAnalysis {
 function getDynamicDistributions(students: Student[]) {
     // 1. Encontrar la nota más alta actual en la clase
     // Si nadie tiene nota, asumimos 1 para evitar división por cero
-    const currentMaxScore = Math.max(...students.map(s => s.accumulated_score), 0) || 10;
+    const currentMaxScore = Math.max(...students.map(s => s.accumulated_score ?? 0), 0) || 10;
 
     let ranges = [];
 
@@ -94,7 +60,7 @@ function getDynamicDistributions(students: Student[]) {
     // 3. Mapear los estudiantes a los rangos calculados
     return ranges.map(r => {
         const studentsInBucket = students.filter((s: Student) => 
-            s.accumulated_score >= r.min && s.accumulated_score < r.max
+            (s.accumulated_score ?? 0) >= r.min && (s.accumulated_score ?? 0) < r.max
         );
         
         return {
@@ -125,7 +91,7 @@ export function useGradeData() {
             const bytes = new Uint8Array(await file.arrayBuffer());
             const ge = createEngine(bytes);
 
-            const summary = ge.get_summary() as GradeData;
+            const summary = ge.get_summary();
             const table = ge.get_table();
 
             // Calculate Distributions
@@ -26,66 +26,30 @@ export function createEngine(bytes: Uint8Array) {
  * Usage example:
  * 
  * ```ts
- * import { GradeEngine } from "../pkg/pkg.js";
+ * import { GradeEngine } from "../../pkg/pna_wasm.js";
  * 
- * const engine = new GradeEngine(csvBytes); // csvBytes is Uint8Array
- * const summary = engine.get_summary();     // JSON summary of class
- * const table = engine.get_table();         // JSON table of scores
+ * const engine = new GradeEngine(bytes);    // bytes is the workbook as a Uint8Array
+ * const summary = engine.get_summary();     // GradeStatsOwned
+ * const table = engine.get_table();         // AcademicTable
  * ```
  * 
- * Methods:
+ * Types:
  * 
- * 1. get_summary(): Returns a summary of the class in JSON format.
+ * The return types of get_summary() and get_table() are generated from the Rust models
+ * (StudentSummary, EvaluationSummary, ClassSummary, AcademicTable, GradeValue, ...) and
+ * ship in pkg/pna_wasm.d.ts, so a change in the engine shows up as a TypeScript error.
  * 
- *    Structure:
- *    {
- *      class: {
- *        student_count: number,       // Total number of students
- *        overall_average: number,     // Class average score
- *        overall_std_dev: number,     // Standard deviation of student averages
- *        approved_count: number,      // Number of students with status "Approved"
- *        at_risk_count: number,       // Number of students with status "AtRisk"
- *        failed_count: number         // Number of students with status "Failed"
- *      },
- *      students: [
- *        {
- *          id: string,               // Student index
- *          name: string,             // Student name
- *          average: number | null,   // Average score of student
- *          percentile: number | null,// Percentile rank of student in class
- *          std_dev: number | null,   // Standard deviation of student scores
- *          status: "Approved" | "AtRisk" | "Failed"
- *        },
- *        ...
- *      ],
- *      evaluations: [
- *        {
- *          id: string,               // Evaluation index
- *          name: string,             // Evaluation name
- *          average: number | null,   // Average score of this evaluation
- *          std_dev: number | null,   // Standard deviation of this evaluation
- *          highest_score: number | null, // Highest score
- *          lowest_score: number | null,  // Lowest score
- *          evaluated_count: number,      // Number of students evaluated
- *          missing_count: number         // Number of missing scores
- *        },
- *        ...
- *      ]
- *    }
- * 
- * 2. get_table(): Returns the raw grade table in JSON format.
- * 
- *    Structure:
- *    {
- *      students: string[],         // Array of student names
- *      evaluations: string[],      // Array of evaluation names
- *      scores: (number | null)[][] // 2D array of scores: [student][evaluation]
- *    }
+ * - get_summary(): { students: StudentSummary[], evaluations: EvaluationSummary[], class: ClassSummary }
+ *   Each student has a status of "Approved" | "OnTrack" | "Warning" | "Critical" | "Failed",
+ *   and the class counts them in approved_count, on_track_count, warning_count,
+ *   critical_count and failed_count.
+ * - get_table(): { evaluations: string[], records: StudentRecord[] }, where each grade is a
+ *   GradeValue tagged by `status` ("Numeric", "Fraction", "Withdrawn", "Absent" or "Label").
  * 
  * Notes:
+ * - Scores the engine cannot compute yet (e.g. no graded evaluations) are `undefined`,
+ *   not `null`.
  * - Use get_summary() for building dashboards, charts, or quick analysis.
  * - Use get_table() if you need access to the raw scores for detailed comparisons or
  *   cross-analysis in the frontend.
- * - All methods return JSON-compatible objects; you can pass them directly to React state
- *   or charting libraries.
  */